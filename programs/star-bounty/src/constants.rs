@@ -6,10 +6,30 @@ pub const POSITION_OWNER_SEED: &[u8] = b"investor_fee_pos_owner";
 pub const POLICY_SEED: &[u8] = b"policy";
 pub const PROGRESS_SEED: &[u8] = b"progress";
 pub const TREASURY_SEED: &[u8] = b"treasury";
+pub const BASE_TREASURY_SEED: &[u8] = b"base_treasury";
+pub const RECEIPT_SEED: &[u8] = b"receipt";
+pub const ACCRUAL_SEED: &[u8] = b"accrual";
+pub const REGISTRY_SEED: &[u8] = b"registry";
+pub const INVESTOR_MARKER_SEED: &[u8] = b"investor_marker";
+pub const INVESTOR_LOCK_SEED: &[u8] = b"investor_lock";
+pub const LOCK_ESCROW_SEED: &[u8] = b"lock_escrow";
+pub const CRON_SEED: &[u8] = b"cron";
+pub const CRON_AUTHORITY_SEED: &[u8] = b"cron_authority";
+
+/// CP-AMM PDA seeds (derived under the CP-AMM program ID)
+pub const POOL_AUTHORITY_SEED: &[u8] = b"pool_authority";
+pub const EVENT_AUTHORITY_SEED: &[u8] = b"__event_authority";
+
+/// Tuktuk cron job settings
+pub const DAILY_CRON_SCHEDULE: &str = "0 0 0 * * *"; // sec min hour: every day at 00:00 UTC
+pub const CRON_JOB_NAME: &str = "star-bounty-daily-crank";
+pub const MAX_CRANK_URL_LEN: usize = 200;
 
 /// Time constants
 pub const SECONDS_PER_DAY: i64 = 86_400; // 24 hours in seconds
 pub const SECONDS_PER_HOUR: i64 = 3_600;
+pub const DEFAULT_FINALIZE_GRACE_SECONDS: i64 = 12 * SECONDS_PER_HOUR; // Finalize with pages pending after this
+pub const MIN_FINALIZE_GRACE_SECONDS: i64 = SECONDS_PER_HOUR;
 
 /// Math constants
 pub const BPS_DENOMINATOR: u64 = 10_000; // 100% = 10,000 basis points
@@ -17,8 +37,13 @@ pub const MAX_INVESTOR_FEE_SHARE_BPS: u16 = 10_000; // 100% maximum
 pub const PERCENTAGE_MULTIPLIER: u64 = 100;
 
 /// Pagination constants
-pub const MAX_INVESTORS_PER_PAGE: usize = 20; // Max investors per distribution page
+// A full payout page (named accounts + program + 4 per investor) must fit the lock limit
+pub const MAX_INVESTORS_PER_PAGE: usize = 10; // Max investors per distribution page
+pub const MAX_TX_ACCOUNT_LOCKS: usize = 64; // Solana per-transaction account limit
 pub const MAX_PAGES_PER_DAY: u16 = 1000; // Safety limit on pagination
+pub const QUOTE_ONLY_COLLECT_FEE_MODE: u8 = 1; // DAMM v2: fees collected in token B only
+pub const SNAPSHOT_ACCOUNTS_PER_INVESTOR: usize = 2; // stream + receipt
+pub const PAYOUT_ACCOUNTS_PER_INVESTOR: usize = 4; // stream + ATA + receipt + accrual
 
 /// Validation constants
 pub const MIN_Y0_ALLOCATION: u64 = 1; // Minimum Y0 allocation
@@ -29,13 +54,6 @@ pub const DEFAULT_MIN_PAYOUT: u64 = 10_000; // 0.01 tokens (6 decimals)
 pub const PROGRAM_VERSION: &str = "1.0.0";
 pub const PROGRAM_NAME: &str = "Star Investor Fees";
 
-/// External program IDs
-pub const METEORA_PROGRAM_ID: Pubkey =
-    anchor_lang::solana_program::pubkey!("24Uqj9JCLxUeoC3hGfh5W3s9FM9uCHDS2SG3LYwBpyTi");
-// pub const STREAMFLOW_PROGRAM_ID: Pubkey = anchor_lang::solana_program::pubkey!("strmRqUCoQUgGUan5YhzUZa6KqdzwX5L6FpUTNYXr6vk");
-pub const TUKTUK_PROGRAM_ID: Pubkey =
-    anchor_lang::solana_program::pubkey!("tuktukUrfhXT6ZT77QTU8RQtvgL967uRuVagWF57zVA");
-
 /// Common token mints (for reference)
 pub const USDC_MINT: Pubkey =
     anchor_lang::solana_program::pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
@@ -81,6 +99,27 @@ pub struct QuoteFeesClaimed {
     pub day_start: i64,
 }
 
+/// Emitted for each page of the daily locked-amount snapshot
+#[event]
+pub struct LockedSnapshotPage {
+    pub vault_id: [u8; 32],
+    pub page_number: u16,
+    pub total_pages: u16,
+    pub locked_this_page: u64,
+    pub total_locked: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a completed snapshot locks more than the Y0 allocation
+#[event]
+pub struct LockedTotalExceedsAllocation {
+    pub vault_id: [u8; 32],
+    pub total_locked: u64,
+    pub y0_total_allocation: u64,
+    pub day_start: i64,
+    pub timestamp: i64,
+}
+
 /// Emitted for each page of investor payouts
 #[event]
 pub struct InvestorPayoutPage {
@@ -91,6 +130,16 @@ pub struct InvestorPayoutPage {
     pub timestamp: i64,
 }
 
+/// Emitted when a day is finalized with investor fees it could not pay (pages pending or no one locked)
+#[event]
+pub struct UnpaidInvestorFeesCarried {
+    pub vault_id: [u8; 32],
+    pub pages_pending: u16,
+    pub amount_carried: u64,
+    pub day_start: i64,
+    pub timestamp: i64,
+}
+
 /// Emitted when a day's distribution is finalized
 #[event]
 pub struct CreatorPayoutDayClosed {
@@ -109,6 +158,7 @@ pub struct PolicyUpdated {
     pub investor_fee_share_bps: u16,
     pub daily_cap_lamports: Option<u64>,
     pub min_payout_lamports: u64,
+    pub finalize_grace_seconds: i64,
     pub timestamp: i64,
 }
 
@@ -120,6 +170,135 @@ pub struct PauseStateChanged {
     pub timestamp: i64,
 }
 
+/// Emitted when an investor is added to the registry
+#[event]
+pub struct InvestorRegistered {
+    pub vault_id: [u8; 32],
+    pub page_index: u16,
+    pub stream: Pubkey,
+    pub recipient: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a vault's quote and base treasuries are created
+#[event]
+pub struct TreasuryInitialized {
+    pub vault_id: [u8; 32],
+    pub quote_treasury: Pubkey,
+    pub base_treasury: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a vault's Tuktuk cron job is registered
+#[event]
+pub struct CronRegistered {
+    pub vault_id: [u8; 32],
+    pub cron_job: Pubkey,
+    pub task_queue: Pubkey,
+    pub crank_url: String,
+    pub crank_signer: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a vault's cron job is topped up
+#[event]
+pub struct CronFunded {
+    pub vault_id: [u8; 32],
+    pub cron_job: Pubkey,
+    pub funder: Pubkey,
+    pub lamports: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a vault's cron job is closed
+#[event]
+pub struct CronCancelled {
+    pub vault_id: [u8; 32],
+    pub cron_job: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a native investor lock is created and funded
+#[event]
+pub struct InvestorLockCreated {
+    pub vault_id: [u8; 32],
+    pub lock: Pubkey,
+    pub recipient: Pubkey,
+    pub total_amount: u64,
+    pub start_time: i64,
+    pub cliff_time: i64,
+    pub end_time: i64,
+    pub cliff_amount: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when an investor withdraws unlocked tokens from a native lock
+#[event]
+pub struct InvestorLockWithdrawn {
+    pub vault_id: [u8; 32],
+    pub lock: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub total_withdrawn: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when an investor is removed from the registry
+#[event]
+pub struct InvestorRemoved {
+    pub vault_id: [u8; 32],
+    pub page_index: u16,
+    pub stream: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the investor Merkle root is set, rotated or cleared
+#[event]
+pub struct InvestorRootUpdated {
+    pub vault_id: [u8; 32],
+    pub previous_root: [u8; 32],
+    pub new_root: [u8; 32],
+    pub investor_count: u32,
+    pub page_size: u16,
+    pub timestamp: i64,
+}
+
+/// Emitted when the creator payout wallet changes
+#[event]
+pub struct CreatorUpdated {
+    pub vault_id: [u8; 32],
+    pub previous_creator: Pubkey,
+    pub new_creator: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a new policy authority is proposed
+#[event]
+pub struct AuthorityTransferProposed {
+    pub vault_id: [u8; 32],
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the pending authority accepts the transfer
+#[event]
+pub struct AuthorityTransferred {
+    pub vault_id: [u8; 32],
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a pending authority transfer is cancelled
+#[event]
+pub struct AuthorityTransferCancelled {
+    pub vault_id: [u8; 32],
+    pub authority: Pubkey,
+    pub cancelled_authority: Pubkey,
+    pub timestamp: i64,
+}
+
 /// Emitted when liquidity is added to position
 #[event]
 pub struct LiquidityAdded {
@@ -142,6 +321,16 @@ pub struct DustAccumulated {
     pub timestamp: i64,
 }
 
+/// Emitted when an investor claims their accrued balance and closes the accrual
+#[event]
+pub struct AccrualClaimed {
+    pub vault_id: [u8; 32],
+    pub stream: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
 /// Emitted for emergency actions
 #[event]
 pub struct EmergencyAction {
@@ -176,7 +365,7 @@ macro_rules! validate_bps {
 macro_rules! safe_add {
     ($a:expr, $b:expr) => {
         $a.checked_add($b)
-            .ok_or(crate::errors::StarInvestorFeesError::ArithmeticOverflow)
+            .ok_or($crate::errors::StarInvestorFeesError::ArithmeticOverflow)
     };
 }
 
@@ -184,7 +373,7 @@ macro_rules! safe_add {
 macro_rules! safe_sub {
     ($a:expr, $b:expr) => {
         $a.checked_sub($b)
-            .ok_or(crate::errors::StarInvestorFeesError::ArithmeticUnderflow)
+            .ok_or($crate::errors::StarInvestorFeesError::ArithmeticUnderflow)
     };
 }
 
@@ -192,7 +381,7 @@ macro_rules! safe_sub {
 macro_rules! safe_mul {
     ($a:expr, $b:expr) => {
         $a.checked_mul($b)
-            .ok_or(crate::errors::StarInvestorFeesError::ArithmeticOverflow)
+            .ok_or($crate::errors::StarInvestorFeesError::ArithmeticOverflow)
     };
 }
 
@@ -200,7 +389,7 @@ macro_rules! safe_mul {
 macro_rules! safe_div {
     ($a:expr, $b:expr) => {{
         if $b == 0 {
-            Err(crate::errors::StarInvestorFeesError::DivisionByZero)
+            Err($crate::errors::StarInvestorFeesError::DivisionByZero)
         } else {
             Ok($a / $b)
         }
@@ -338,7 +527,7 @@ mod tests {
         assert_eq!(BPS_DENOMINATOR, 10_000);
         assert_eq!(MAX_INVESTOR_FEE_SHARE_BPS, 10_000);
         assert_eq!(SECONDS_PER_DAY, 86_400);
-        assert_eq!(MAX_INVESTORS_PER_PAGE, 20);
+        assert_eq!(MAX_INVESTORS_PER_PAGE, 10);
     }
 
     #[test]
@@ -379,6 +568,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::assertions_on_constants)]
     fn test_config_presets() {
         assert!(ConservativeConfig::INVESTOR_FEE_SHARE_BPS <= MAX_INVESTOR_FEE_SHARE_BPS);
         assert!(ModerateConfig::INVESTOR_FEE_SHARE_BPS <= MAX_INVESTOR_FEE_SHARE_BPS);
@@ -405,6 +595,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::assertions_on_constants)]
     fn test_tick_constants() {
         assert!(MIN_TICK < 0);
         assert!(MAX_TICK > 0);
@@ -0,0 +1,300 @@
+//! Read-only view of Meteora DAMM v2 (CP-AMM) accounts
+//!
+//! Offsets follow the zero-copy `Pool` layout of the CP-AMM program; each
+//! accessor reads straight from the account data without copying it.
+
+use crate::constants::{EVENT_AUTHORITY_SEED, POOL_AUTHORITY_SEED};
+use crate::errors::StarInvestorFeesError;
+use anchor_lang::prelude::*;
+use std::cell::Ref;
+use std::ops::Deref;
+
+/// Byte offsets of the `Pool` fields we read (including the 8-byte discriminator)
+pub mod pool_layout {
+    /// Anchor discriminator: sha256("account:Pool")[..8]
+    pub const DISCRIMINATOR: [u8; 8] = [241, 154, 109, 4, 17, 177, 109, 188];
+
+    // pool_fees.base_fee
+    pub const CLIFF_FEE_NUMERATOR: usize = 8;
+    pub const FEE_SCHEDULER_MODE: usize = 16;
+    pub const NUMBER_OF_PERIOD: usize = 22;
+    pub const PERIOD_FREQUENCY: usize = 24;
+    pub const REDUCTION_FACTOR: usize = 32;
+    // pool_fees
+    pub const PROTOCOL_FEE_PERCENT: usize = 48;
+    pub const PARTNER_FEE_PERCENT: usize = 49;
+    pub const REFERRAL_FEE_PERCENT: usize = 50;
+    pub const DYNAMIC_FEE_INITIALIZED: usize = 56;
+
+    pub const TOKEN_A_MINT: usize = 168;
+    pub const TOKEN_B_MINT: usize = 200;
+    pub const TOKEN_A_VAULT: usize = 232;
+    pub const TOKEN_B_VAULT: usize = 264;
+    pub const LIQUIDITY: usize = 360;
+    pub const SQRT_MIN_PRICE: usize = 424;
+    pub const SQRT_MAX_PRICE: usize = 440;
+    pub const SQRT_PRICE: usize = 456;
+    pub const POOL_STATUS: usize = 481;
+    pub const COLLECT_FEE_MODE: usize = 484;
+
+    /// Minimum account size covering every field we read
+    pub const MIN_LEN: usize = COLLECT_FEE_MODE + 1;
+}
+
+/// CP-AMM's global pool authority PDA
+pub fn pool_authority(cp_amm_program_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[POOL_AUTHORITY_SEED], cp_amm_program_id).0
+}
+
+/// CP-AMM's Anchor event authority PDA
+pub fn event_authority(cp_amm_program_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[EVENT_AUTHORITY_SEED], cp_amm_program_id).0
+}
+
+/// Base fee schedule and fee split of a pool
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DammV2FeeSettings {
+    pub cliff_fee_numerator: u64,
+    pub fee_scheduler_mode: u8,
+    pub number_of_period: u16,
+    pub period_frequency: u64,
+    pub reduction_factor: u64,
+    pub protocol_fee_percent: u8,
+    pub partner_fee_percent: u8,
+    pub referral_fee_percent: u8,
+    pub dynamic_fee_enabled: bool,
+}
+
+/// Typed, read-only view over a DAMM v2 `Pool` account's data
+pub struct DammV2Pool<D: Deref<Target = [u8]>> {
+    data: D,
+}
+
+impl<'a> DammV2Pool<Ref<'a, [u8]>> {
+    /// Borrow a pool account, checking its owner and discriminator
+    pub fn load(pool: &'a AccountInfo, cp_amm_program_id: &Pubkey) -> Result<Self> {
+        require_keys_eq!(
+            *pool.owner,
+            *cp_amm_program_id,
+            StarInvestorFeesError::InvalidAccountOwner
+        );
+
+        let data = Ref::map(pool.try_borrow_data()?, |data| &**data);
+        Self::new(data)
+    }
+}
+
+impl<D: Deref<Target = [u8]>> DammV2Pool<D> {
+    /// Wrap raw pool data, checking its length and discriminator
+    pub fn new(data: D) -> Result<Self> {
+        require!(
+            data.len() >= pool_layout::MIN_LEN && data[..8] == pool_layout::DISCRIMINATOR,
+            StarInvestorFeesError::InvalidPoolConfig
+        );
+        Ok(Self { data })
+    }
+
+    fn read_u8(&self, offset: usize) -> u8 {
+        self.data[offset]
+    }
+
+    fn read_u16(&self, offset: usize) -> u16 {
+        u16::from_le_bytes(self.data[offset..offset + 2].try_into().unwrap())
+    }
+
+    fn read_u64(&self, offset: usize) -> u64 {
+        u64::from_le_bytes(self.data[offset..offset + 8].try_into().unwrap())
+    }
+
+    fn read_u128(&self, offset: usize) -> u128 {
+        u128::from_le_bytes(self.data[offset..offset + 16].try_into().unwrap())
+    }
+
+    fn read_pubkey(&self, offset: usize) -> Pubkey {
+        Pubkey::new_from_array(self.data[offset..offset + 32].try_into().unwrap())
+    }
+
+    pub fn token_a_mint(&self) -> Pubkey {
+        self.read_pubkey(pool_layout::TOKEN_A_MINT)
+    }
+
+    pub fn token_b_mint(&self) -> Pubkey {
+        self.read_pubkey(pool_layout::TOKEN_B_MINT)
+    }
+
+    pub fn token_a_vault(&self) -> Pubkey {
+        self.read_pubkey(pool_layout::TOKEN_A_VAULT)
+    }
+
+    pub fn token_b_vault(&self) -> Pubkey {
+        self.read_pubkey(pool_layout::TOKEN_B_VAULT)
+    }
+
+    pub fn liquidity(&self) -> u128 {
+        self.read_u128(pool_layout::LIQUIDITY)
+    }
+
+    pub fn sqrt_min_price(&self) -> u128 {
+        self.read_u128(pool_layout::SQRT_MIN_PRICE)
+    }
+
+    pub fn sqrt_max_price(&self) -> u128 {
+        self.read_u128(pool_layout::SQRT_MAX_PRICE)
+    }
+
+    pub fn sqrt_price(&self) -> u128 {
+        self.read_u128(pool_layout::SQRT_PRICE)
+    }
+
+    pub fn pool_status(&self) -> u8 {
+        self.read_u8(pool_layout::POOL_STATUS)
+    }
+
+    /// 0 = fees in both tokens, 1 = fees in token B only
+    pub fn collect_fee_mode(&self) -> u8 {
+        self.read_u8(pool_layout::COLLECT_FEE_MODE)
+    }
+
+    pub fn fee_settings(&self) -> DammV2FeeSettings {
+        DammV2FeeSettings {
+            cliff_fee_numerator: self.read_u64(pool_layout::CLIFF_FEE_NUMERATOR),
+            fee_scheduler_mode: self.read_u8(pool_layout::FEE_SCHEDULER_MODE),
+            number_of_period: self.read_u16(pool_layout::NUMBER_OF_PERIOD),
+            period_frequency: self.read_u64(pool_layout::PERIOD_FREQUENCY),
+            reduction_factor: self.read_u64(pool_layout::REDUCTION_FACTOR),
+            protocol_fee_percent: self.read_u8(pool_layout::PROTOCOL_FEE_PERCENT),
+            partner_fee_percent: self.read_u8(pool_layout::PARTNER_FEE_PERCENT),
+            referral_fee_percent: self.read_u8(pool_layout::REFERRAL_FEE_PERCENT),
+            dynamic_fee_enabled: self.read_u8(pool_layout::DYNAMIC_FEE_INITIALIZED) != 0,
+        }
+    }
+
+    /// Require the pool to trade `base_mint`/`quote_mint` as token A/B and
+    /// collect fees in the quote token only
+    pub fn validate_quote_only(
+        &self,
+        base_mint: &Pubkey,
+        quote_mint: &Pubkey,
+        base_vault: &Pubkey,
+        quote_vault: &Pubkey,
+    ) -> Result<()> {
+        require_keys_eq!(
+            self.token_a_mint(),
+            *base_mint,
+            StarInvestorFeesError::InvalidPoolTokenOrder
+        );
+        require_keys_eq!(
+            self.token_b_mint(),
+            *quote_mint,
+            StarInvestorFeesError::InvalidQuoteMint
+        );
+        require_keys_eq!(
+            self.token_a_vault(),
+            *base_vault,
+            StarInvestorFeesError::InvalidPoolConfig
+        );
+        require_keys_eq!(
+            self.token_b_vault(),
+            *quote_vault,
+            StarInvestorFeesError::InvalidPoolConfig
+        );
+        require!(
+            self.collect_fee_mode() == crate::constants::QUOTE_ONLY_COLLECT_FEE_MODE,
+            StarInvestorFeesError::InvalidFeeMode
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool_fixture(collect_fee_mode: u8) -> (Vec<u8>, [Pubkey; 4]) {
+        let keys = [
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        ];
+
+        let mut data = vec![0u8; 1112];
+        data[..8].copy_from_slice(&pool_layout::DISCRIMINATOR);
+        data[8..16].copy_from_slice(&2_500_000u64.to_le_bytes());
+        data[22..24].copy_from_slice(&12u16.to_le_bytes());
+        data[48] = 20;
+        for (key, offset) in keys.iter().zip([168, 200, 232, 264]) {
+            data[offset..offset + 32].copy_from_slice(key.as_ref());
+        }
+        data[424..440].copy_from_slice(&4_295_048_016u128.to_le_bytes());
+        data[440..456].copy_from_slice(&79_226_673_521_066_979_257_578_248_091u128.to_le_bytes());
+        data[456..472].copy_from_slice(&(1u128 << 64).to_le_bytes());
+        data[484] = collect_fee_mode;
+
+        (data, keys)
+    }
+
+    #[test]
+    fn test_pool_accessors() {
+        let (data, [mint_a, mint_b, vault_a, vault_b]) = pool_fixture(1);
+        let pool = DammV2Pool::new(&data[..]).unwrap();
+
+        assert_eq!(pool.token_a_mint(), mint_a);
+        assert_eq!(pool.token_b_mint(), mint_b);
+        assert_eq!(pool.token_a_vault(), vault_a);
+        assert_eq!(pool.token_b_vault(), vault_b);
+        assert_eq!(pool.sqrt_min_price(), 4_295_048_016);
+        assert_eq!(
+            pool.sqrt_max_price(),
+            79_226_673_521_066_979_257_578_248_091
+        );
+        assert_eq!(pool.sqrt_price(), 1u128 << 64);
+        assert_eq!(pool.collect_fee_mode(), 1);
+
+        let fees = pool.fee_settings();
+        assert_eq!(fees.cliff_fee_numerator, 2_500_000);
+        assert_eq!(fees.number_of_period, 12);
+        assert_eq!(fees.protocol_fee_percent, 20);
+        assert!(!fees.dynamic_fee_enabled);
+
+        assert!(pool
+            .validate_quote_only(&mint_a, &mint_b, &vault_a, &vault_b)
+            .is_ok());
+        // Swapped token order is rejected
+        assert!(pool
+            .validate_quote_only(&mint_b, &mint_a, &vault_a, &vault_b)
+            .is_err());
+    }
+
+    #[test]
+    fn test_pool_rejects_both_token_fee_mode() {
+        let (data, [mint_a, mint_b, vault_a, vault_b]) = pool_fixture(0);
+        let pool = DammV2Pool::new(&data[..]).unwrap();
+
+        assert_eq!(
+            pool.validate_quote_only(&mint_a, &mint_b, &vault_a, &vault_b)
+                .unwrap_err(),
+            StarInvestorFeesError::InvalidFeeMode.into()
+        );
+    }
+
+    #[test]
+    fn test_cp_amm_pdas() {
+        let cp_amm = crate::external_programs::CP_AMM_PROGRAM_ID;
+
+        assert_eq!(
+            pool_authority(&cp_amm),
+            anchor_lang::solana_program::pubkey!("HLnpSz9h2S4hiLQ43rnSD9XkcUThA7B8hQMKmDaiTLcC")
+        );
+        assert_ne!(event_authority(&cp_amm), pool_authority(&cp_amm));
+    }
+
+    #[test]
+    fn test_pool_rejects_bad_data() {
+        let (mut data, _) = pool_fixture(1);
+
+        assert!(DammV2Pool::new(&data[..pool_layout::MIN_LEN - 1]).is_err());
+        data[0] ^= 1;
+        assert!(DammV2Pool::new(&data[..]).is_err());
+    }
+}
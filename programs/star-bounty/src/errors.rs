@@ -4,11 +4,14 @@ use anchor_lang::prelude::*;
 ///
 /// Errors are organized by category for easier debugging and monitoring.
 /// Each error includes a descriptive message that will be shown on-chain.
+///
+/// Discriminants are relative: Anchor adds its 6000 offset, so the category
+/// ranges below are the codes clients see.
 #[error_code]
 pub enum StarInvestorFeesError {
     // ========== Position Initialization Errors (6000-6009) ==========
     #[msg("Base token fees detected - this position must only accrue quote fees")]
-    BaseFeesDetected = 6000,
+    BaseFeesDetected = 0,
 
     #[msg("Invalid quote mint - does not match pool configuration")]
     InvalidQuoteMint,
@@ -39,7 +42,7 @@ pub enum StarInvestorFeesError {
 
     // ========== Distribution Timing Errors (6010-6019) ==========
     #[msg("Distribution can only be called once per 24 hours - please wait")]
-    DistributionTooEarly,
+    DistributionTooEarly = 10,
 
     #[msg("Day distribution already finalized - start a new day")]
     DayAlreadyFinalized,
@@ -58,7 +61,7 @@ pub enum StarInvestorFeesError {
 
     // ========== Configuration Errors (6020-6029) ==========
     #[msg("Investor fee share exceeds maximum allowed (10000 bps = 100%)")]
-    InvalidFeeShareBps,
+    InvalidFeeShareBps = 20,
 
     #[msg("Invalid page number - must process pages sequentially starting from 0")]
     InvalidPageNumber,
@@ -81,9 +84,15 @@ pub enum StarInvestorFeesError {
     #[msg("Policy configuration is missing required parameters")]
     PolicyConfigInvalid,
 
+    #[msg("Invalid lock schedule - requires start <= cliff <= end and cliff amount <= total")]
+    InvalidLockSchedule,
+
+    #[msg("Finalize grace window must be between one hour and one day")]
+    InvalidFinalizeGrace,
+
     // ========== Data Integrity Errors (6030-6039) ==========
     #[msg("Total locked amount exceeds Y0 allocation - data integrity issue")]
-    InvalidLockedTotal,
+    InvalidLockedTotal = 30,
 
     #[msg("Zero total locked amount - no distribution needed")]
     ZeroTotalLocked,
@@ -103,9 +112,18 @@ pub enum StarInvestorFeesError {
     #[msg("Investor data is corrupted or invalid")]
     InvalidInvestorData,
 
+    #[msg("Investor stream is not in the registry for this page")]
+    InvestorNotRegistered,
+
+    #[msg("Investor stream is already registered")]
+    InvestorAlreadyRegistered,
+
+    #[msg("Merkle proof does not match the investor root")]
+    InvalidMerkleProof,
+
     // ========== Arithmetic Errors (6040-6049) ==========
     #[msg("Arithmetic overflow detected - amounts too large")]
-    ArithmeticOverflow,
+    ArithmeticOverflow = 40,
 
     #[msg("Arithmetic underflow detected - invalid subtraction")]
     ArithmeticUnderflow,
@@ -121,7 +139,7 @@ pub enum StarInvestorFeesError {
 
     // ========== External Program Errors (6050-6059) ==========
     #[msg("Invalid Streamflow stream account - cannot read vesting data")]
-    InvalidStreamflowAccount,
+    InvalidStreamflowAccount = 50,
 
     #[msg("Streamflow stream data deserialization failed - format mismatch")]
     StreamflowDeserializationFailed,
@@ -141,9 +159,18 @@ pub enum StarInvestorFeesError {
     #[msg("External program returned unexpected data")]
     ExternalProgramDataInvalid,
 
+    #[msg("Investor lock does not vest the project's base mint")]
+    StreamMintMismatch,
+
+    #[msg("Lock program does not match the policy's lock source")]
+    InvalidLockProgram,
+
+    #[msg("Lock account is not owned by the lock program or is malformed")]
+    InvalidLockAccount,
+
     // ========== Access Control Errors (6060-6069) ==========
     #[msg("Invalid authority - only the policy authority can perform this action")]
-    InvalidAuthority,
+    InvalidAuthority = 60,
 
     #[msg("Program is paused - operations temporarily disabled")]
     ProgramPaused,
@@ -160,9 +187,15 @@ pub enum StarInvestorFeesError {
     #[msg("Cranker is not authorized for this operation")]
     InvalidCranker,
 
+    #[msg("No authority transfer is pending")]
+    NoPendingAuthority,
+
+    #[msg("Signer is not the pending authority")]
+    InvalidPendingAuthority,
+
     // ========== Account Validation Errors (6070-6079) ==========
     #[msg("Treasury ATA does not match expected derivation")]
-    InvalidTreasuryAta,
+    InvalidTreasuryAta = 70,
 
     #[msg("Invalid investor token account - must match quote mint")]
     InvalidInvestorAta,
@@ -182,9 +215,15 @@ pub enum StarInvestorFeesError {
     #[msg("Account has insufficient rent exemption")]
     InsufficientRent,
 
+    #[msg("Investor receipt does not match expected derivation")]
+    InvalidInvestorReceipt,
+
+    #[msg("Investor accrual does not match expected derivation")]
+    InvalidInvestorAccrual,
+
     // ========== Pagination Errors (6080-6089) ==========
-    #[msg("Too many investors in page - maximum 20 per page")]
-    TooManyInvestorsPerPage,
+    #[msg("Too many investors in page - maximum 10 per page")]
+    TooManyInvestorsPerPage = 80,
 
     #[msg("Inconsistent investor data - stream/ATA count mismatch")]
     InconsistentInvestorData,
@@ -192,7 +231,7 @@ pub enum StarInvestorFeesError {
     #[msg("Page already processed - cannot reprocess")]
     PageAlreadyProcessed,
 
-    #[msg("Invalid remaining accounts - must be even number (stream + ATA pairs)")]
+    #[msg("Invalid remaining accounts - wrong number of accounts per investor")]
     InvalidRemainingAccounts,
 
     #[msg("Pagination cursor is invalid")]
@@ -201,9 +240,21 @@ pub enum StarInvestorFeesError {
     #[msg("Cannot skip pages - must process sequentially")]
     CannotSkipPages,
 
+    #[msg("Locked snapshot incomplete - snapshot every page before payouts")]
+    SnapshotIncomplete,
+
+    #[msg("Investor stream already processed for this day - duplicate entry")]
+    DuplicateInvestor,
+
+    #[msg("Investor stream was not included in this day's snapshot")]
+    InvestorNotInSnapshot,
+
+    #[msg("Registry page is full - maximum 20 investors per page")]
+    RegistryPageFull,
+
     // ========== Fee Distribution Errors (6090-6099) ==========
     #[msg("No fees available to distribute")]
-    NoFeesToDistribute,
+    NoFeesToDistribute = 90,
 
     #[msg("Fee claim returned zero amount")]
     ZeroFeeClaim,
@@ -223,9 +274,15 @@ pub enum StarInvestorFeesError {
     #[msg("Claim instruction failed")]
     ClaimInstructionFailed,
 
+    #[msg("Nothing unlocked to withdraw")]
+    NothingToWithdraw,
+
+    #[msg("Investor accrual still holds a balance")]
+    AccrualNotEmpty,
+
     // ========== Tuktuk Integration Errors (6100-6109) ==========
     #[msg("Tuktuk task queue not found")]
-    TuktukTaskQueueNotFound,
+    TuktukTaskQueueNotFound = 100,
 
     #[msg("Tuktuk cron job creation failed")]
     TuktukCronCreationFailed,
@@ -241,6 +298,15 @@ pub enum StarInvestorFeesError {
 
     #[msg("Invalid fee mode: only quote-token fee collection (mode = 1) is supported.")]
     InvalidFeeMode,
+
+    #[msg("Invalid Tuktuk or Tuktuk cron program")]
+    InvalidTuktukProgram,
+
+    #[msg("Crank URL must be non-empty and within the length limit, with a non-default signer")]
+    InvalidCrankConfig,
+
+    #[msg("Cron job does not match the vault's registered cron job")]
+    CronJobMismatch,
 }
 
 impl StarInvestorFeesError {
@@ -299,7 +365,7 @@ impl StarInvestorFeesError {
             Self::CpAmmFeeCollectionFailed => "Failed to collect fees from pool.",
 
             // Pagination
-            Self::TooManyInvestorsPerPage => "Too many investors in one page. Maximum is 20.",
+            Self::TooManyInvestorsPerPage => "Too many investors in one page. Maximum is 10.",
             Self::InconsistentInvestorData => "Investor data is mismatched.",
             Self::PageAlreadyProcessed => "This page was already processed.",
 
@@ -338,7 +404,7 @@ impl StarInvestorFeesError {
 
     /// Returns the error category for logging/monitoring
     pub fn category(&self) -> ErrorCategory {
-        let code = *self as u32;
+        let code = u32::from(*self);
 
         match code {
             6000..=6009 => ErrorCategory::Initialization,
@@ -366,7 +432,7 @@ impl StarInvestorFeesError {
             Self::InsufficientTreasuryBalance => "Wait for fee accrual or trigger fee claim",
             Self::BaseFeesDetected => "Recreate position with correct tick range",
             Self::InvalidAuthority => "Use authorized wallet",
-            Self::TooManyInvestorsPerPage => "Split into multiple pages of max 20 investors",
+            Self::TooManyInvestorsPerPage => "Split into multiple pages of max 10 investors",
             Self::ZeroTotalLocked => "No action needed - normal when all tokens unlocked",
             _ => "Check documentation or contact support",
         }
@@ -478,18 +544,29 @@ mod tests {
 
     #[test]
     fn test_error_code_ranges() {
-        // Test that error codes are in expected ranges
-        assert_eq!(StarInvestorFeesError::BaseFeesDetected as u32, 6000);
-        assert_eq!(StarInvestorFeesError::DistributionTooEarly as u32, 6010);
-        assert_eq!(StarInvestorFeesError::InvalidFeeShareBps as u32, 6020);
-        assert_eq!(StarInvestorFeesError::InvalidLockedTotal as u32, 6030);
-        assert_eq!(StarInvestorFeesError::ArithmeticOverflow as u32, 6040);
-        assert_eq!(StarInvestorFeesError::InvalidStreamflowAccount as u32, 6050);
-        assert_eq!(StarInvestorFeesError::InvalidAuthority as u32, 6060);
-        assert_eq!(StarInvestorFeesError::InvalidTreasuryAta as u32, 6070);
-        assert_eq!(StarInvestorFeesError::TooManyInvestorsPerPage as u32, 6080);
-        assert_eq!(StarInvestorFeesError::NoFeesToDistribute as u32, 6090);
-        assert_eq!(StarInvestorFeesError::TuktukTaskQueueNotFound as u32, 6100);
+        // Codes as clients see them, after Anchor's offset
+        for (error, code) in [
+            (StarInvestorFeesError::BaseFeesDetected, 6000),
+            (StarInvestorFeesError::DistributionTooEarly, 6010),
+            (StarInvestorFeesError::InvalidFeeShareBps, 6020),
+            (StarInvestorFeesError::InvalidLockedTotal, 6030),
+            (StarInvestorFeesError::ArithmeticOverflow, 6040),
+            (StarInvestorFeesError::InvalidStreamflowAccount, 6050),
+            (StarInvestorFeesError::InvalidAuthority, 6060),
+            (StarInvestorFeesError::InvalidTreasuryAta, 6070),
+            (StarInvestorFeesError::TooManyInvestorsPerPage, 6080),
+            (StarInvestorFeesError::NoFeesToDistribute, 6090),
+            (StarInvestorFeesError::TuktukTaskQueueNotFound, 6100),
+            (StarInvestorFeesError::CronJobMismatch, 6108),
+        ] {
+            assert_eq!(u32::from(error), code);
+            match Error::from(error) {
+                Error::AnchorError(anchor_error) => {
+                    assert_eq!(anchor_error.error_code_number, code)
+                }
+                Error::ProgramError(_) => panic!("expected an Anchor error"),
+            }
+        }
     }
 
     #[test]
@@ -516,7 +593,7 @@ mod tests {
         assert!(error.recommended_action().contains("authorized wallet"));
 
         let error = StarInvestorFeesError::TooManyInvestorsPerPage;
-        assert!(error.recommended_action().contains("20"));
+        assert!(error.recommended_action().contains("10"));
     }
 }
 // InvestorFeesError::BaseFeesDetected.is_recoverable());
@@ -0,0 +1,125 @@
+//! IDs of the external programs this program talks to
+//!
+//! The cluster is picked with a cargo feature: `mainnet` (the default when no
+//! cluster feature is set), `devnet` or `localnet`. Every account constraint
+//! and CPI must take its program ID from here so all instructions target the
+//! same deployment.
+
+use anchor_lang::prelude::*;
+
+#[cfg(any(
+    all(feature = "mainnet", feature = "devnet"),
+    all(feature = "mainnet", feature = "localnet"),
+    all(feature = "devnet", feature = "localnet")
+))]
+compile_error!("enable at most one of the `mainnet`, `devnet` and `localnet` features");
+
+/// Mainnet-beta deployments
+pub mod mainnet {
+    use anchor_lang::prelude::*;
+
+    pub const CP_AMM: Pubkey =
+        anchor_lang::solana_program::pubkey!("cpamdpZCGKUy5JxQXB4dcpGPiikHawvSWAd6mEn1sGG");
+    pub const STREAMFLOW: Pubkey =
+        anchor_lang::solana_program::pubkey!("strmRqUCoQUgGUan5YhzUZa6KqdzwX5L6FpUTNYXr6m");
+    pub const TUKTUK: Pubkey =
+        anchor_lang::solana_program::pubkey!("tuktukUrfhXT6ZT77QTU8RQtvgL967uRuVagWF57zVA");
+    pub const TUKTUK_CRON: Pubkey =
+        anchor_lang::solana_program::pubkey!("cronAjRZnJn3MTP3B9kE62NWDrjSuAPVXf9c4hu4grM");
+}
+
+/// Devnet deployments
+pub mod devnet {
+    use anchor_lang::prelude::*;
+
+    pub const CP_AMM: Pubkey =
+        anchor_lang::solana_program::pubkey!("cpamdpZCGKUy5JxQXB4dcpGPiikHawvSWAd6mEn1sGG");
+    pub const STREAMFLOW: Pubkey =
+        anchor_lang::solana_program::pubkey!("HqDGZjaVRXJ9MGRQEw7qDc2rAr6iH1n1kAQdCZaCMfMZ");
+    pub const TUKTUK: Pubkey =
+        anchor_lang::solana_program::pubkey!("tuktukUrfhXT6ZT77QTU8RQtvgL967uRuVagWF57zVA");
+    pub const TUKTUK_CRON: Pubkey =
+        anchor_lang::solana_program::pubkey!("cronAjRZnJn3MTP3B9kE62NWDrjSuAPVXf9c4hu4grM");
+}
+
+/// Local validator with the devnet programs cloned at their devnet addresses
+pub mod localnet {
+    pub use super::devnet::*;
+}
+
+#[cfg(not(any(feature = "devnet", feature = "localnet")))]
+use mainnet as cluster;
+
+#[cfg(feature = "devnet")]
+use devnet as cluster;
+
+#[cfg(feature = "localnet")]
+use localnet as cluster;
+
+/// Meteora DAMM v2 (CP-AMM)
+pub const CP_AMM_PROGRAM_ID: Pubkey = cluster::CP_AMM;
+
+/// Tuktuk task scheduler
+pub const TUKTUK_PROGRAM_ID: Pubkey = cluster::TUKTUK;
+
+/// Tuktuk cron (schedules recurring transactions on a task queue)
+pub const TUKTUK_CRON_PROGRAM_ID: Pubkey = cluster::TUKTUK_CRON;
+
+/// Jupiter Lock (same address on every cluster)
+pub const JUPITER_LOCK_PROGRAM_ID: Pubkey =
+    anchor_lang::solana_program::pubkey!("LocpQgucEQHbqNABEYvBvwoxCPsSbG91A1QaQhQQqjn");
+
+/// Streamflow protocol
+pub const STREAMFLOW_PROGRAM_ID: Pubkey = cluster::STREAMFLOW;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cluster_program_ids() {
+        assert_eq!(
+            mainnet::STREAMFLOW.to_string(),
+            "strmRqUCoQUgGUan5YhzUZa6KqdzwX5L6FpUTNYXr6m"
+        );
+        assert_eq!(
+            devnet::STREAMFLOW.to_string(),
+            "HqDGZjaVRXJ9MGRQEw7qDc2rAr6iH1n1kAQdCZaCMfMZ"
+        );
+        assert_eq!(localnet::STREAMFLOW, devnet::STREAMFLOW);
+
+        for (cp_amm, tuktuk, tuktuk_cron) in [
+            (mainnet::CP_AMM, mainnet::TUKTUK, mainnet::TUKTUK_CRON),
+            (devnet::CP_AMM, devnet::TUKTUK, devnet::TUKTUK_CRON),
+            (localnet::CP_AMM, localnet::TUKTUK, localnet::TUKTUK_CRON),
+        ] {
+            assert_eq!(
+                cp_amm.to_string(),
+                "cpamdpZCGKUy5JxQXB4dcpGPiikHawvSWAd6mEn1sGG"
+            );
+            assert_eq!(
+                tuktuk.to_string(),
+                "tuktukUrfhXT6ZT77QTU8RQtvgL967uRuVagWF57zVA"
+            );
+            assert_eq!(
+                tuktuk_cron.to_string(),
+                "cronAjRZnJn3MTP3B9kE62NWDrjSuAPVXf9c4hu4grM"
+            );
+        }
+    }
+
+    #[test]
+    fn test_selected_cluster() {
+        #[cfg(not(any(feature = "devnet", feature = "localnet")))]
+        let expected = mainnet::STREAMFLOW;
+        #[cfg(feature = "devnet")]
+        let expected = devnet::STREAMFLOW;
+        #[cfg(feature = "localnet")]
+        let expected = localnet::STREAMFLOW;
+
+        assert_eq!(STREAMFLOW_PROGRAM_ID, expected);
+        assert_eq!(CP_AMM_PROGRAM_ID, cluster::CP_AMM);
+        assert_eq!(TUKTUK_PROGRAM_ID, cluster::TUKTUK);
+        assert_eq!(TUKTUK_CRON_PROGRAM_ID, cluster::TUKTUK_CRON);
+    }
+}
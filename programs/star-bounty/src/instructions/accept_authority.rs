@@ -0,0 +1,44 @@
+use crate::constants::*;
+use crate::errors::StarInvestorFeesError;
+use crate::state::PolicyConfig;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(vault_id: [u8; 32])]
+pub struct AcceptAuthority<'info> {
+    /// Proposed authority (must sign to prove control of the key)
+    pub pending_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [POLICY_SEED, vault_id.as_ref()],
+        bump = policy.bump,
+        constraint = policy.pending_authority.is_some() @ StarInvestorFeesError::NoPendingAuthority,
+        constraint = policy.pending_authority == Some(pending_authority.key())
+            @ StarInvestorFeesError::InvalidPendingAuthority
+    )]
+    pub policy: Account<'info, PolicyConfig>,
+}
+
+pub fn handler(ctx: Context<AcceptAuthority>, _vault_id: [u8; 32]) -> Result<()> {
+    let policy = &mut ctx.accounts.policy;
+
+    let previous_authority = policy.authority;
+    policy.authority = ctx.accounts.pending_authority.key();
+    policy.pending_authority = None;
+
+    emit!(AuthorityTransferred {
+        vault_id: policy.vault_id,
+        previous_authority,
+        new_authority: policy.authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Authority transferred from {} to {}",
+        previous_authority,
+        policy.authority
+    );
+
+    Ok(())
+}
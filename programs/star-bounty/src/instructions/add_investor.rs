@@ -0,0 +1,94 @@
+use crate::constants::*;
+use crate::errors::StarInvestorFeesError;
+use crate::state::{
+    DistributionProgress, InvestorMarker, InvestorRegistryPage, PolicyConfig, RegistryEntry,
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(vault_id: [u8; 32], page_index: u16, stream: Pubkey)]
+pub struct AddInvestor<'info> {
+    #[account(
+        mut,
+        constraint = authority.key() == policy.authority @ StarInvestorFeesError::InvalidAuthority
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [POLICY_SEED, vault_id.as_ref()],
+        bump = policy.bump
+    )]
+    pub policy: Account<'info, PolicyConfig>,
+
+    /// Distribution progress (page layout cannot change mid-day)
+    #[account(
+        seeds = [PROGRESS_SEED, vault_id.as_ref()],
+        bump = progress.bump,
+        constraint = !progress.is_day_in_progress() @ StarInvestorFeesError::DistributionInProgress
+    )]
+    pub progress: Account<'info, DistributionProgress>,
+
+    #[account(
+        mut,
+        seeds = [REGISTRY_SEED, vault_id.as_ref(), &page_index.to_le_bytes()],
+        bump = registry_page.bump
+    )]
+    pub registry_page: Account<'info, InvestorRegistryPage>,
+
+    /// One marker per stream across all pages; init fails if it is already registered
+    #[account(
+        init,
+        payer = authority,
+        space = InvestorMarker::LEN,
+        seeds = [INVESTOR_MARKER_SEED, vault_id.as_ref(), stream.as_ref()],
+        bump
+    )]
+    pub investor_marker: Account<'info, InvestorMarker>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<AddInvestor>,
+    vault_id: [u8; 32],
+    page_index: u16,
+    stream: Pubkey,
+    recipient: Pubkey,
+) -> Result<()> {
+    let registry_page = &mut ctx.accounts.registry_page;
+
+    require!(
+        !registry_page.is_full(),
+        StarInvestorFeesError::RegistryPageFull
+    );
+    require!(
+        registry_page.find(&stream).is_none(),
+        StarInvestorFeesError::InvestorAlreadyRegistered
+    );
+    require!(
+        recipient != Pubkey::default(),
+        StarInvestorFeesError::InvalidInvestorData
+    );
+
+    registry_page
+        .entries
+        .push(RegistryEntry { stream, recipient });
+
+    let investor_marker = &mut ctx.accounts.investor_marker;
+    investor_marker.vault_id = vault_id;
+    investor_marker.stream = stream;
+    investor_marker.page_index = page_index;
+    investor_marker.bump = ctx.bumps.investor_marker;
+
+    emit!(InvestorRegistered {
+        vault_id,
+        page_index,
+        stream,
+        recipient,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Investor {} registered on page {}", stream, page_index);
+
+    Ok(())
+}
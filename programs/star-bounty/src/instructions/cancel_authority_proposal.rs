@@ -0,0 +1,40 @@
+use crate::constants::*;
+use crate::errors::StarInvestorFeesError;
+use crate::state::PolicyConfig;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(vault_id: [u8; 32])]
+pub struct CancelAuthorityProposal<'info> {
+    #[account(
+        constraint = authority.key() == policy.authority @ StarInvestorFeesError::InvalidAuthority
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [POLICY_SEED, vault_id.as_ref()],
+        bump = policy.bump
+    )]
+    pub policy: Account<'info, PolicyConfig>,
+}
+
+pub fn handler(ctx: Context<CancelAuthorityProposal>, _vault_id: [u8; 32]) -> Result<()> {
+    let policy = &mut ctx.accounts.policy;
+
+    let cancelled_authority = policy
+        .pending_authority
+        .take()
+        .ok_or(StarInvestorFeesError::NoPendingAuthority)?;
+
+    emit!(AuthorityTransferCancelled {
+        vault_id: policy.vault_id,
+        authority: policy.authority,
+        cancelled_authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Authority transfer to {} cancelled", cancelled_authority);
+
+    Ok(())
+}
@@ -0,0 +1,151 @@
+use crate::constants::*;
+use crate::errors::StarInvestorFeesError;
+use crate::external_programs::TUKTUK_CRON_PROGRAM_ID;
+use crate::state::{CronConfig, PolicyConfig};
+use crate::tuktuk::{self, CRANK_TRANSACTION_INDEX};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(vault_id: [u8; 32])]
+pub struct CancelCron<'info> {
+    /// Policy authority receiving the refunded rent and remaining job balance
+    #[account(
+        mut,
+        constraint = authority.key() == policy.authority @ StarInvestorFeesError::InvalidAuthority
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [POLICY_SEED, vault_id.as_ref()],
+        bump = policy.bump
+    )]
+    pub policy: Account<'info, PolicyConfig>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [CRON_SEED, vault_id.as_ref()],
+        bump = cron_config.bump
+    )]
+    pub cron_config: Account<'info, CronConfig>,
+
+    /// PDA owning the cron job
+    /// CHECK: Seeds validated, signs the cron CPIs
+    #[account(
+        seeds = [VAULT_SEED, vault_id.as_ref(), CRON_AUTHORITY_SEED],
+        bump = cron_config.authority_bump
+    )]
+    pub cron_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Derived from the cron authority
+    #[account(
+        mut,
+        seeds = [tuktuk::USER_CRON_JOBS_SEED, cron_authority.key().as_ref()],
+        bump,
+        seeds::program = cron_program.key()
+    )]
+    pub user_cron_jobs: UncheckedAccount<'info>,
+
+    /// CHECK: Must be the vault's registered cron job
+    #[account(
+        mut,
+        constraint = cron_job.key() == cron_config.cron_job @ StarInvestorFeesError::CronJobMismatch
+    )]
+    pub cron_job: UncheckedAccount<'info>,
+
+    /// CHECK: Derived from the cron authority and job name
+    #[account(
+        mut,
+        seeds = [
+            tuktuk::CRON_JOB_NAME_MAPPING_SEED,
+            cron_authority.key().as_ref(),
+            CRON_JOB_NAME.as_bytes()
+        ],
+        bump,
+        seeds::program = cron_program.key()
+    )]
+    pub cron_job_name_mapping: UncheckedAccount<'info>,
+
+    /// CHECK: Derived from the cron job
+    #[account(
+        mut,
+        seeds = [
+            tuktuk::CRON_JOB_TRANSACTION_SEED,
+            cron_job.key().as_ref(),
+            &CRANK_TRANSACTION_INDEX.to_le_bytes()
+        ],
+        bump,
+        seeds::program = cron_program.key()
+    )]
+    pub cron_job_transaction: UncheckedAccount<'info>,
+
+    /// CHECK: Scratch account used by the cron program
+    #[account(mut)]
+    pub task_return_account_1: UncheckedAccount<'info>,
+
+    /// CHECK: Scratch account used by the cron program
+    #[account(mut)]
+    pub task_return_account_2: UncheckedAccount<'info>,
+
+    /// CHECK: Program ID validated
+    #[account(
+        constraint = cron_program.key() == TUKTUK_CRON_PROGRAM_ID
+            @ StarInvestorFeesError::InvalidTuktukProgram
+    )]
+    pub cron_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CancelCron>, vault_id: [u8; 32]) -> Result<()> {
+    let seeds = &[
+        VAULT_SEED,
+        vault_id.as_ref(),
+        CRON_AUTHORITY_SEED,
+        &[ctx.accounts.cron_config.authority_bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let accounts = &ctx.accounts;
+    let mut cron_authority_signer = accounts.cron_authority.to_account_info();
+    cron_authority_signer.is_signer = true;
+
+    // A job can only be closed once its transactions are removed
+    tuktuk::invoke_cron(
+        &accounts.cron_program,
+        &[
+            accounts.authority.to_account_info(),
+            cron_authority_signer.clone(),
+            accounts.cron_job.to_account_info(),
+            accounts.cron_job_transaction.to_account_info(),
+        ],
+        tuktuk::remove_transaction_data(CRANK_TRANSACTION_INDEX)?,
+        signer_seeds,
+    )?;
+
+    tuktuk::invoke_cron(
+        &accounts.cron_program,
+        &[
+            accounts.authority.to_account_info(),
+            cron_authority_signer,
+            accounts.user_cron_jobs.to_account_info(),
+            accounts.cron_job.to_account_info(),
+            accounts.cron_job_name_mapping.to_account_info(),
+            accounts.task_return_account_1.to_account_info(),
+            accounts.task_return_account_2.to_account_info(),
+            accounts.system_program.to_account_info(),
+        ],
+        tuktuk::close_cron_job_data(),
+        signer_seeds,
+    )?;
+
+    msg!("Cron job cancelled: {}", accounts.cron_job.key());
+
+    emit!(CronCancelled {
+        vault_id,
+        cron_job: accounts.cron_job.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
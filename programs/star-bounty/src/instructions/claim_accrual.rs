@@ -0,0 +1,145 @@
+use crate::constants::*;
+use crate::errors::StarInvestorFeesError;
+use crate::state::{DistributionProgress, InvestorAccrual, InvestorReceipt, PolicyConfig};
+use crate::utils::{safe_math, validation};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+#[derive(Accounts)]
+#[instruction(vault_id: [u8; 32])]
+pub struct ClaimAccrual<'info> {
+    /// Investor the accrual is owed to (the lock recipient at the last snapshot)
+    pub recipient: Signer<'info>,
+
+    #[account(
+        seeds = [POLICY_SEED, vault_id.as_ref()],
+        bump = policy.bump
+    )]
+    pub policy: Account<'info, PolicyConfig>,
+
+    /// Distribution progress (tracks the total accrued across investors)
+    #[account(
+        mut,
+        seeds = [PROGRESS_SEED, vault_id.as_ref()],
+        bump = progress.bump
+    )]
+    pub progress: Account<'info, DistributionProgress>,
+
+    /// Accrual being paid out and closed
+    #[account(
+        mut,
+        seeds = [ACCRUAL_SEED, vault_id.as_ref(), accrual.stream.as_ref()],
+        bump = accrual.bump,
+        has_one = rent_payer @ StarInvestorFeesError::InvalidInvestorAccrual,
+        close = rent_payer
+    )]
+    pub accrual: Box<Account<'info, InvestorAccrual>>,
+
+    /// Receipt of the same stream, naming the investor it pays
+    #[account(
+        seeds = [RECEIPT_SEED, vault_id.as_ref(), accrual.stream.as_ref()],
+        bump = receipt.bump,
+        constraint = receipt.recipient == recipient.key() @ StarInvestorFeesError::UnauthorizedSigner
+    )]
+    pub receipt: Box<Account<'info, InvestorReceipt>>,
+
+    /// Cranker that paid the accrual's rent (refunded on close)
+    /// CHECK: Matched against the accrual's recorded rent payer
+    #[account(mut)]
+    pub rent_payer: UncheckedAccount<'info>,
+
+    /// Position owner PDA (treasury authority)
+    /// CHECK: Seeds validated
+    #[account(
+        seeds = [VAULT_SEED, vault_id.as_ref(), POSITION_OWNER_SEED],
+        bump
+    )]
+    pub position_owner_pda: UncheckedAccount<'info>,
+
+    /// Program treasury ATA holding the accrued balance
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, vault_id.as_ref()],
+        bump,
+        token::mint = quote_mint,
+        token::authority = position_owner_pda,
+        token::token_program = token_program
+    )]
+    pub treasury_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Investor's quote token account
+    #[account(
+        mut,
+        token::mint = quote_mint,
+        token::authority = recipient,
+        token::token_program = token_program
+    )]
+    pub recipient_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Quote mint
+    #[account(
+        mint::token_program = token_program,
+        constraint = quote_mint.key() == policy.quote_mint @ StarInvestorFeesError::InvalidQuoteMint
+    )]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Token program owning the quote mint (legacy SPL Token or Token-2022)
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler(ctx: Context<ClaimAccrual>, vault_id: [u8; 32]) -> Result<()> {
+    let amount = ctx.accounts.accrual.accrued;
+
+    // Pays whatever has accrued, below the minimum or not; an empty accrual is just closed
+    if amount > 0 {
+        let position_owner_bump = ctx.bumps.position_owner_pda;
+        let seeds = &[
+            VAULT_SEED,
+            vault_id.as_ref(),
+            POSITION_OWNER_SEED,
+            &[position_owner_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.treasury_ata.to_account_info(),
+                    mint: ctx.accounts.quote_mint.to_account_info(),
+                    to: ctx.accounts.recipient_ata.to_account_info(),
+                    authority: ctx.accounts.position_owner_pda.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            ctx.accounts.quote_mint.decimals,
+        )?;
+    }
+
+    let progress = &mut ctx.accounts.progress;
+    progress.carry_over_dust = safe_math::sub(progress.carry_over_dust, amount)?;
+
+    // The treasury must still cover everything else investors are owed
+    ctx.accounts.treasury_ata.reload()?;
+    validation::validate_treasury_covers(
+        ctx.accounts.treasury_ata.amount,
+        progress.outstanding_investor_fees(),
+    )?;
+
+    emit!(AccrualClaimed {
+        vault_id,
+        stream: ctx.accounts.accrual.stream,
+        recipient: ctx.accounts.recipient.key(),
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Accrual of {} claimed for stream {}",
+        amount,
+        ctx.accounts.accrual.stream
+    );
+
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use crate::constants::*;
+use crate::errors::StarInvestorFeesError;
+use crate::state::InvestorAccrual;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(vault_id: [u8; 32])]
+pub struct CloseInvestorAccrual<'info> {
+    /// Cranker that paid the accrual's rent
+    #[account(mut)]
+    pub rent_payer: Signer<'info>,
+
+    /// Only an empty accrual can be closed; a balance is claimed by the investor
+    #[account(
+        mut,
+        seeds = [ACCRUAL_SEED, vault_id.as_ref(), accrual.stream.as_ref()],
+        bump = accrual.bump,
+        has_one = rent_payer @ StarInvestorFeesError::UnauthorizedSigner,
+        constraint = accrual.accrued == 0 @ StarInvestorFeesError::AccrualNotEmpty,
+        close = rent_payer
+    )]
+    pub accrual: Account<'info, InvestorAccrual>,
+}
+
+pub fn handler(ctx: Context<CloseInvestorAccrual>, _vault_id: [u8; 32]) -> Result<()> {
+    msg!("Accrual for stream {} closed", ctx.accounts.accrual.stream);
+
+    Ok(())
+}
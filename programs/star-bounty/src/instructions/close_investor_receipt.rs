@@ -0,0 +1,53 @@
+use crate::constants::*;
+use crate::errors::StarInvestorFeesError;
+use crate::state::{DistributionProgress, InvestorReceipt};
+use crate::utils::accruals;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(vault_id: [u8; 32])]
+pub struct CloseInvestorReceipt<'info> {
+    /// Cranker that paid the receipt's rent
+    #[account(mut)]
+    pub rent_payer: Signer<'info>,
+
+    /// Distribution progress (receipts guard the day's snapshot and payouts)
+    #[account(
+        seeds = [PROGRESS_SEED, vault_id.as_ref()],
+        bump = progress.bump,
+        constraint = !progress.is_day_in_progress() @ StarInvestorFeesError::DistributionInProgress
+    )]
+    pub progress: Account<'info, DistributionProgress>,
+
+    #[account(
+        mut,
+        seeds = [RECEIPT_SEED, vault_id.as_ref(), receipt.stream.as_ref()],
+        bump = receipt.bump,
+        has_one = rent_payer @ StarInvestorFeesError::UnauthorizedSigner,
+        close = rent_payer
+    )]
+    pub receipt: Account<'info, InvestorReceipt>,
+
+    /// Accrual of the same stream; the investor claims it through the receipt
+    /// CHECK: PDA validated in handler, may not exist yet
+    pub accrual: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<CloseInvestorReceipt>, vault_id: [u8; 32]) -> Result<()> {
+    let stream = ctx.accounts.receipt.stream;
+
+    // Closing the receipt must not strand a balance the investor can still claim
+    let accrued = accruals::load_if_exists(
+        &ctx.accounts.accrual.to_account_info(),
+        &stream,
+        &vault_id,
+        ctx.program_id,
+    )?
+    .map(|accrual| accrual.accrued)
+    .unwrap_or(0);
+    require!(accrued == 0, StarInvestorFeesError::AccrualNotEmpty);
+
+    msg!("Receipt for stream {} closed", stream);
+
+    Ok(())
+}
@@ -0,0 +1,137 @@
+use crate::constants::*;
+use crate::errors::StarInvestorFeesError;
+use crate::state::{InvestorLock, PolicyConfig};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+#[derive(Accounts)]
+#[instruction(vault_id: [u8; 32], recipient: Pubkey)]
+pub struct CreateInvestorLock<'info> {
+    /// Policy authority funding the lock
+    #[account(
+        mut,
+        constraint = authority.key() == policy.authority @ StarInvestorFeesError::InvalidAuthority
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [POLICY_SEED, vault_id.as_ref()],
+        bump = policy.bump
+    )]
+    pub policy: Account<'info, PolicyConfig>,
+
+    /// Lock account for this investor
+    #[account(
+        init,
+        payer = authority,
+        space = InvestorLock::LEN,
+        seeds = [INVESTOR_LOCK_SEED, vault_id.as_ref(), recipient.as_ref()],
+        bump
+    )]
+    pub investor_lock: Account<'info, InvestorLock>,
+
+    /// Escrow token account holding the locked base tokens
+    #[account(
+        init,
+        payer = authority,
+        seeds = [LOCK_ESCROW_SEED, investor_lock.key().as_ref()],
+        bump,
+        token::mint = base_mint,
+        token::authority = investor_lock,
+        token::token_program = token_program
+    )]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+
+    /// Base (project) mint
+    #[account(
+        constraint = base_mint.key() == policy.base_mint @ StarInvestorFeesError::InvalidPoolTokenOrder
+    )]
+    pub base_mint: InterfaceAccount<'info, Mint>,
+
+    /// Authority's base token account funding the escrow
+    #[account(
+        mut,
+        token::mint = base_mint,
+        token::authority = authority,
+        token::token_program = token_program
+    )]
+    pub funder_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn handler(
+    ctx: Context<CreateInvestorLock>,
+    vault_id: [u8; 32],
+    recipient: Pubkey,
+    amount: u64,
+    start_time: i64,
+    cliff_time: i64,
+    end_time: i64,
+    cliff_amount: u64,
+) -> Result<()> {
+    require!(
+        recipient != Pubkey::default(),
+        StarInvestorFeesError::InvalidInvestorData
+    );
+
+    // Fund the escrow
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.funder_token_account.to_account_info(),
+                mint: ctx.accounts.base_mint.to_account_info(),
+                to: ctx.accounts.escrow.to_account_info(),
+                authority: ctx.accounts.authority.to_account_info(),
+            },
+        ),
+        amount,
+        ctx.accounts.base_mint.decimals,
+    )?;
+
+    // Token-2022 transfer fees may withhold part of the deposit; lock what arrived
+    ctx.accounts.escrow.reload()?;
+    let total_amount = ctx.accounts.escrow.amount;
+
+    let investor_lock = &mut ctx.accounts.investor_lock;
+    investor_lock.vault_id = vault_id;
+    investor_lock.recipient = recipient;
+    investor_lock.mint = ctx.accounts.base_mint.key();
+    investor_lock.total_amount = total_amount;
+    investor_lock.withdrawn_amount = 0;
+    investor_lock.start_time = start_time;
+    investor_lock.cliff_time = cliff_time;
+    investor_lock.end_time = end_time;
+    investor_lock.cliff_amount = cliff_amount;
+    investor_lock.bump = ctx.bumps.investor_lock;
+    investor_lock.escrow_bump = ctx.bumps.escrow;
+
+    require!(
+        investor_lock.is_valid_schedule(),
+        StarInvestorFeesError::InvalidLockSchedule
+    );
+
+    emit!(InvestorLockCreated {
+        vault_id,
+        lock: investor_lock.key(),
+        recipient,
+        total_amount,
+        start_time,
+        cliff_time,
+        end_time,
+        cliff_amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Investor lock created for {}: {} locked",
+        recipient,
+        total_amount
+    );
+
+    Ok(())
+}
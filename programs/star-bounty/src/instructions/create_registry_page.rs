@@ -0,0 +1,66 @@
+use crate::constants::*;
+use crate::errors::StarInvestorFeesError;
+use crate::state::{DistributionProgress, InvestorRegistryPage, PolicyConfig};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(vault_id: [u8; 32])]
+pub struct CreateRegistryPage<'info> {
+    #[account(
+        mut,
+        constraint = authority.key() == policy.authority @ StarInvestorFeesError::InvalidAuthority
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [POLICY_SEED, vault_id.as_ref()],
+        bump = policy.bump
+    )]
+    pub policy: Account<'info, PolicyConfig>,
+
+    /// Distribution progress (page layout cannot change mid-day)
+    #[account(
+        seeds = [PROGRESS_SEED, vault_id.as_ref()],
+        bump = progress.bump,
+        constraint = !progress.is_day_in_progress() @ StarInvestorFeesError::DistributionInProgress
+    )]
+    pub progress: Account<'info, DistributionProgress>,
+
+    /// Next registry page (always appended after the last one)
+    #[account(
+        init,
+        payer = authority,
+        space = InvestorRegistryPage::LEN,
+        seeds = [REGISTRY_SEED, vault_id.as_ref(), &policy.registry_pages.to_le_bytes()],
+        bump
+    )]
+    pub registry_page: Account<'info, InvestorRegistryPage>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CreateRegistryPage>, vault_id: [u8; 32]) -> Result<()> {
+    let policy = &mut ctx.accounts.policy;
+
+    require!(
+        policy.registry_pages < MAX_PAGES_PER_DAY,
+        StarInvestorFeesError::InvalidPaginationCursor
+    );
+
+    let registry_page = &mut ctx.accounts.registry_page;
+    registry_page.vault_id = vault_id;
+    registry_page.page_index = policy.registry_pages;
+    registry_page.entries = Vec::new();
+    registry_page.bump = ctx.bumps.registry_page;
+
+    policy.registry_pages += 1;
+
+    msg!(
+        "Registry page {} created ({} pages total)",
+        registry_page.page_index,
+        policy.registry_pages
+    );
+
+    Ok(())
+}
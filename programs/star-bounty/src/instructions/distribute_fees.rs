@@ -1,13 +1,18 @@
-use anchor_lang::prelude::*;
-use anchor_lang::solana_program::instruction::Instruction;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 use crate::constants::*;
+use crate::damm_v2::DammV2Pool;
 use crate::errors::StarInvestorFeesError;
-use crate::state::{PolicyConfig, DistributionProgress, StreamflowStream};
-use crate::utils::{safe_math, validation, fee_calc, events, logging};
+use crate::external_programs::CP_AMM_PROGRAM_ID;
+use crate::state::{DistributionProgress, InvestorProof, InvestorRegistryPage, PolicyConfig};
+use crate::utils::{
+    accruals, events, fee_calc, investors, logging, receipts, safe_math, validation,
+};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 
 #[derive(Accounts)]
-#[instruction(vault_id: [u8; 32])]
+#[instruction(vault_id: [u8; 32], page_number: u16)]
 pub struct DistributeFees<'info> {
     /// Cranker who executes the distribution (can be anyone - permissionless)
     #[account(mut)]
@@ -29,6 +34,13 @@ pub struct DistributeFees<'info> {
     )]
     pub progress: Account<'info, DistributionProgress>,
 
+    /// Registry page listing the investors on this page (omitted when the policy uses a Merkle root)
+    #[account(
+        seeds = [REGISTRY_SEED, vault_id.as_ref(), &page_number.to_le_bytes()],
+        bump = registry_page.bump
+    )]
+    pub registry_page: Option<Box<Account<'info, InvestorRegistryPage>>>,
+
     /// Position owner PDA that controls the honorary position
     /// CHECK: Seeds validated, used for signing
     #[account(
@@ -37,9 +49,10 @@ pub struct DistributeFees<'info> {
     )]
     pub position_owner_pda: UncheckedAccount<'info>,
 
-    /// Honorary position account
+    /// Honorary position account (written by the claim CPI)
     /// CHECK: Validated against policy
     #[account(
+        mut,
         constraint = position.key() == policy.position @ StarInvestorFeesError::InvalidAuthority
     )]
     pub position: UncheckedAccount<'info>,
@@ -49,10 +62,21 @@ pub struct DistributeFees<'info> {
         mut,
         seeds = [TREASURY_SEED, vault_id.as_ref()],
         bump,
+        token::token_program = token_program,
         constraint = treasury_ata.mint == policy.quote_mint @ StarInvestorFeesError::InvalidQuoteMint,
         constraint = treasury_ata.owner == position_owner_pda.key() @ StarInvestorFeesError::InvalidTreasuryAta
     )]
-    pub treasury_ata: Account<'info, TokenAccount>,
+    pub treasury_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Program base treasury (receives any base-side fees from the claim)
+    #[account(
+        mut,
+        seeds = [BASE_TREASURY_SEED, vault_id.as_ref()],
+        bump,
+        constraint = base_treasury.mint == pool_base_vault.mint @ StarInvestorFeesError::InvalidPoolTokenOrder,
+        constraint = base_treasury.owner == position_owner_pda.key() @ StarInvestorFeesError::InvalidTreasuryAta
+    )]
+    pub base_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// Pool account
     /// CHECK: Validated against policy
@@ -66,44 +90,78 @@ pub struct DistributeFees<'info> {
         mut,
         constraint = pool_quote_vault.mint == policy.quote_mint @ StarInvestorFeesError::InvalidQuoteMint
     )]
-    pub pool_quote_vault: Account<'info, TokenAccount>,
+    pub pool_quote_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// Pool's base token vault (for validation - should never receive fees)
     #[account(
+        mut,
         constraint = pool_base_vault.mint != policy.quote_mint @ StarInvestorFeesError::InvalidPoolTokenOrder
     )]
-    pub pool_base_vault: Account<'info, TokenAccount>,
+    pub pool_base_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Quote mint (token B of the pool)
+    #[account(
+        mint::token_program = token_program,
+        constraint = quote_mint.key() == policy.quote_mint @ StarInvestorFeesError::InvalidQuoteMint
+    )]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Base mint (token A of the pool)
+    #[account(
+        constraint = base_mint.key() == pool_base_vault.mint @ StarInvestorFeesError::InvalidPoolTokenOrder,
+        constraint = base_mint.key() == policy.base_mint @ StarInvestorFeesError::InvalidPoolTokenOrder
+    )]
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Pool authority (Meteora PDA)
+    /// CHECK: Derived from the CP-AMM program ID
+    #[account(
+        seeds = [POOL_AUTHORITY_SEED],
+        bump,
+        seeds::program = cp_amm_program.key()
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    /// CP-AMM event authority
+    /// CHECK: Derived from the CP-AMM program ID
+    #[account(
+        seeds = [EVENT_AUTHORITY_SEED],
+        bump,
+        seeds::program = cp_amm_program.key()
+    )]
+    pub event_authority: UncheckedAccount<'info>,
+
+    /// Position NFT account (owned by position_owner_pda)
+    #[account(
+        constraint = position_nft_account.amount == 1 @ StarInvestorFeesError::InvalidPositionOwner,
+        constraint = position_nft_account.owner == position_owner_pda.key() @ StarInvestorFeesError::InvalidPositionOwner
+    )]
+    pub position_nft_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// CP-AMM program
     /// CHECK: Program ID validated
     #[account(
-        constraint = cp_amm_program.key() == anchor_lang::solana_program::pubkey!("CPAmmL9tg1U4bCUQ38Kkdq1rF53tGPY1Hxj3pzBNXwYG")
+        constraint = cp_amm_program.key() == CP_AMM_PROGRAM_ID
             @ StarInvestorFeesError::InvalidCpAmmProgram
     )]
     pub cp_amm_program: UncheckedAccount<'info>,
 
-    /// Streamflow program
-    /// CHECK: Program ID validated
-    #[account(
-        constraint = streamflow_program.key() == anchor_lang::solana_program::pubkey!("strmRqUCoQUgGUan5YhzUZa6KqdzwX5L6FpUTNYXr6vk")
-            @ StarInvestorFeesError::InvalidStreamflowProgram
-    )]
-    pub streamflow_program: UncheckedAccount<'info>,
+    /// Token program owning the quote mint (legacy SPL Token or Token-2022)
+    pub token_program: Interface<'info, TokenInterface>,
 
-    /// Token program
-    pub token_program: Program<'info, Token>,
+    /// Token program owning the base mint
+    pub base_token_program: Interface<'info, TokenInterface>,
 
     /// System program
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(
-    ctx: Context<DistributeFees>,
+pub fn handler<'info>(
+    mut ctx: Context<'_, '_, 'info, 'info, DistributeFees<'info>>,
     vault_id: [u8; 32],
     page_number: u16,
+    proofs: Vec<InvestorProof>,
 ) -> Result<()> {
-    let policy = &ctx.accounts.policy;
-    let progress = &mut ctx.accounts.progress;
     let current_ts = Clock::get()?.unix_timestamp;
 
     msg!("=== Distribution Crank Started ===");
@@ -112,82 +170,107 @@ pub fn handler(
     msg!("Timestamp: {}", current_ts);
 
     // Validate vault ID matches
-    validation::validate_vault_id(&progress.vault_id, &vault_id)?;
+    validation::validate_vault_id(&ctx.accounts.progress.vault_id, &vault_id)?;
+
+    {
+        let progress = &ctx.accounts.progress;
 
-    // Check if this is the first call of a new day (page 0)
-    let is_new_day = page_number == 0 && progress.is_new_day_ready(current_ts);
+        // Ensure day is not already finalized
+        require!(
+            !progress.day_finalized,
+            StarInvestorFeesError::DayAlreadyFinalized
+        );
 
-    if is_new_day {
-        msg!("Starting new distribution day");
+        // Payouts use the global locked total, so every page must be snapshotted first
+        require!(
+            progress.is_snapshot_complete(),
+            StarInvestorFeesError::SnapshotIncomplete
+        );
 
-        // Enforce 24h gating
+        // Validate sequential page processing
+        validation::validate_page_number(page_number, progress.current_page)?;
         require!(
-            progress.is_new_day_ready(current_ts),
-            StarInvestorFeesError::DistributionTooEarly
+            page_number < progress.total_pages,
+            StarInvestorFeesError::InvalidPageNumber
         );
+    }
 
-        // Get treasury balance before claiming
-        let treasury_balance_before = ctx.accounts.treasury_ata.amount;
-        msg!("Treasury balance before claim: {}", treasury_balance_before);
+    if page_number == 0 {
+        msg!("First payout page - claiming fees for the day");
 
         // Claim fees from the honorary position via CP-AMM
-        let claimed_amount = claim_fees_from_position(&ctx)?;
+        let (claimed_amount, claimed_base) = claim_fees_from_position(&mut ctx, vault_id)?;
 
-        msg!("Fees claimed: {}", claimed_amount);
+        msg!(
+            "Fees claimed: {} quote, {} base",
+            claimed_amount,
+            claimed_base
+        );
 
         // Validate we received quote tokens only (no base tokens)
-        validate_quote_only_claim(&ctx, claimed_amount)?;
+        validate_quote_only_claim(claimed_amount, claimed_base)?;
 
-        // Require non-zero claim
-        require!(
-            claimed_amount > 0,
-            StarInvestorFeesError::NoFeesToDistribute
-        );
+        // A zero-fee day still walks every page so it can be finalized
+        if claimed_amount == 0 {
+            msg!("No fees accrued today, only carried dust is distributed");
+        }
 
-        // Reset progress for new day
-        progress.reset_for_new_day(current_ts, claimed_amount);
-        progress.last_distribution_ts = current_ts;
+        let policy = &ctx.accounts.policy;
+        let progress = &mut ctx.accounts.progress;
+        progress.daily_claimed_amount = claimed_amount;
+
+        // Fix the investor pool for the whole day from the global locked total,
+        // plus fees a timed-out day left owed to investors (not subject to the cap)
+        let todays_investor_fee = fee_calc::calculate_investor_pool(
+            claimed_amount,
+            progress.total_locked_snapshot,
+            policy.y0_total_allocation,
+            policy.investor_fee_share_bps,
+            policy.daily_cap_lamports,
+        )?;
+        progress.investor_fee_pool =
+            safe_math::add(todays_investor_fee, progress.owed_from_previous_days())?;
 
-        // Emit fees claimed event
-        events::emit_fees_claimed(vault_id, claimed_amount, current_ts)?;
+        msg!(
+            "Total locked (all pages): {}",
+            progress.total_locked_snapshot
+        );
+        msg!("Total investor fee pool: {}", progress.investor_fee_pool);
 
-        msg!("New day initialized with {} fees", claimed_amount);
+        // Emit fees claimed event
+        events::emit_fees_claimed(vault_id, claimed_amount, progress.current_day_start)?;
     } else {
-        // Not a new day - validate we're continuing an existing day
-        msg!("Continuing distribution for current day");
-
-        // Validate sequential page processing
-        validation::validate_page_number(page_number, progress.current_page)?;
-
-        // Ensure day is not already finalized
-        require!(
-            !progress.day_finalized,
-            StarInvestorFeesError::DayAlreadyFinalized
-        );
+        let progress = &ctx.accounts.progress;
 
+        msg!("Continuing distribution for current day");
         msg!("Current day started at: {}", progress.current_day_start);
         msg!("Total claimed this day: {}", progress.daily_claimed_amount);
-        msg!("Already distributed: {}", progress.daily_distributed_to_investors);
+        msg!(
+            "Already distributed: {}",
+            progress.daily_distributed_to_investors
+        );
     }
 
+    let policy = &ctx.accounts.policy;
+    let progress = &mut ctx.accounts.progress;
+
     // Get remaining accounts (investor data)
     let remaining_accounts = &ctx.remaining_accounts;
 
-    // Validate remaining accounts (must be pairs: stream + ATA)
-    require!(
-        remaining_accounts.len() % 2 == 0,
-        StarInvestorFeesError::InvalidRemainingAccounts
-    );
-
-    let investor_count = remaining_accounts.len() / 2;
+    // Remaining accounts come in (stream, ATA, receipt, accrual) groups
+    let streams = investors::page_streams(remaining_accounts, PAYOUT_ACCOUNTS_PER_INVESTOR)?;
+    let investor_count = streams.len();
 
     msg!("Processing {} investors on this page", investor_count);
 
-    // Validate investor count
-    require!(
-        investor_count <= MAX_INVESTORS_PER_PAGE,
-        StarInvestorFeesError::TooManyInvestorsPerPage
-    );
+    // Every investor on the page must be registered or proven against the root
+    let page_investors = investors::resolve_page(
+        policy,
+        ctx.accounts.registry_page.as_deref().map(|page| &**page),
+        page_number,
+        &streams,
+        &proofs,
+    )?;
 
     // If no investors on this page, skip
     if investor_count == 0 {
@@ -196,84 +279,55 @@ pub fn handler(
         return Ok(());
     }
 
-    // Calculate total locked across all investors on this page
-    let mut total_locked_this_page = 0u64;
+    // Locked amounts come from the day's snapshot receipts
+    let snapshot_day = progress.current_day_start;
+    let total_locked = progress.total_locked_snapshot;
+    let investor_fee_to_distribute = progress.investor_fee_pool;
+
     let mut investor_locked_amounts: Vec<u64> = Vec::with_capacity(investor_count);
+    let mut investor_recipients: Vec<Pubkey> = Vec::with_capacity(investor_count);
 
-    msg!("Reading Streamflow lock data for {} investors", investor_count);
+    msg!("Reading snapshot receipts for {} investors", investor_count);
 
     for i in 0..investor_count {
-        let stream_account_info = &remaining_accounts[i * 2];
-
-        // Deserialize Streamflow stream data
-        let stream_data = parse_streamflow_stream(stream_account_info)?;
+        let stream_account_info = &remaining_accounts[i * PAYOUT_ACCOUNTS_PER_INVESTOR];
+        let receipt_info = &remaining_accounts[i * PAYOUT_ACCOUNTS_PER_INVESTOR + 2];
+
+        // Each stream must have been snapshotted today and may only be paid once
+        let mut receipt = receipts::load(
+            receipt_info,
+            stream_account_info.key,
+            &vault_id,
+            ctx.program_id,
+        )?;
+        require!(
+            receipt.last_snapshot_day == snapshot_day,
+            StarInvestorFeesError::InvestorNotInSnapshot
+        );
+        require!(
+            receipt.last_paid_day != snapshot_day,
+            StarInvestorFeesError::DuplicateInvestor
+        );
+        receipt.last_paid_day = snapshot_day;
+        receipts::save(receipt_info, &receipt)?;
 
-        // Calculate still-locked amount at current time
-        let locked_amount = stream_data.calculate_locked_amount(current_ts as u64);
+        // Pay from the snapshotted weight so numerators always sum to the snapshot total;
+        // the lock itself may have changed since
+        let locked_amount = receipt.snapshot_locked_amount;
 
         msg!("Investor {}: locked = {}", i, locked_amount);
 
         investor_locked_amounts.push(locked_amount);
-
-        total_locked_this_page = safe_math::add(total_locked_this_page, locked_amount)?;
-    }
-
-    msg!("Total locked on page: {}", total_locked_this_page);
-
-    // Skip if no locked amounts
-    if total_locked_this_page == 0 {
-        msg!("No locked tokens found, skipping page {}", page_number);
-        progress.current_page = safe_math::add(progress.current_page as u64, 1)? as u16;
-        return Ok(());
+        investor_recipients.push(receipt.recipient);
     }
 
-    // Validate total locked doesn't exceed Y0
-    require!(
-        total_locked_this_page <= policy.y0_total_allocation,
-        StarInvestorFeesError::InvalidLockedTotal
-    );
-
-    // Calculate f_locked(t) = total_locked / Y0
-    let f_locked = fee_calc::calculate_f_locked(total_locked_this_page, policy.y0_total_allocation)?;
-
-    msg!("f_locked = {} bps", f_locked);
-
-    // Calculate eligible investor share
-    // eligible_investor_share_bps = min(investor_fee_share_bps, floor(f_locked(t) * 10000))
-    let eligible_investor_share_bps = fee_calc::calculate_eligible_share(
-        policy.investor_fee_share_bps,
-        f_locked,
+    // An empty pool or nothing locked still walks the page, so accruals of
+    // fully unlocked streams are flushed every day
+    msg!(
+        "Amount to distribute across all pages: {}",
+        investor_fee_to_distribute
     );
 
-    msg!("Eligible investor share: {} bps ({}%)",
-         eligible_investor_share_bps,
-         eligible_investor_share_bps as f64 / 100.0);
-
-    // Calculate total investor fee for this day
-    // investor_fee_quote = floor(claimed_quote * eligible_investor_share_bps / 10000)
-    let total_claimable = safe_math::add(progress.daily_claimed_amount, progress.carry_over_dust)?;
-    let investor_fee_quote = fee_calc::calculate_investor_fee(
-        total_claimable,
-        eligible_investor_share_bps,
-    )?;
-
-    msg!("Total investor fee pool: {}", investor_fee_quote);
-
-    // Apply daily cap if configured
-    let mut investor_fee_to_distribute = investor_fee_quote;
-
-    if let Some(cap) = policy.daily_cap_lamports {
-        let already_distributed = progress.daily_distributed_to_investors;
-        let remaining_cap = cap.saturating_sub(already_distributed);
-
-        if investor_fee_to_distribute > remaining_cap {
-            msg!("Daily cap applied: {} -> {}", investor_fee_to_distribute, remaining_cap);
-            investor_fee_to_distribute = remaining_cap;
-        }
-    }
-
-    msg!("Amount to distribute this page: {}", investor_fee_to_distribute);
-
     // Distribute to investors pro-rata based on locked amounts
     let mut total_paid_this_page = 0u64;
     let mut investors_paid = 0u16;
@@ -292,38 +346,78 @@ pub fn handler(
 
     for i in 0..investor_count {
         let locked_amount = investor_locked_amounts[i];
-
-        if locked_amount == 0 {
-            msg!("Investor {} has no locked tokens, skipping", i);
-            continue;
-        }
+        let stream = remaining_accounts[i * PAYOUT_ACCOUNTS_PER_INVESTOR].key;
+        let accrual_info = &remaining_accounts[i * PAYOUT_ACCOUNTS_PER_INVESTOR + 3];
+
+        // A fully unlocked stream earns nothing more, so whatever it accrued is flushed
+        // in full; without an accrual there is nothing to do
+        let min_payout = if locked_amount == 0 {
+            let accrued =
+                accruals::load_if_exists(accrual_info, stream, &vault_id, ctx.program_id)?
+                    .map(|accrual| accrual.accrued)
+                    .unwrap_or(0);
+            if accrued == 0 {
+                msg!("Investor {} has no locked tokens, skipping", i);
+                continue;
+            }
+            0
+        } else {
+            policy.min_payout_lamports
+        };
 
         // Calculate proportional payout
         // payout = floor(investor_fee_quote * weight_i(t))
-        // weight_i(t) = locked_i(t) / locked_total(t)
+        // weight_i(t) = locked_i(t) / locked_total(t), locked_total across all pages
         let payout = fee_calc::calculate_proportional_payout(
             investor_fee_to_distribute,
             locked_amount,
-            total_locked_this_page,
+            total_locked,
         )?;
 
-        msg!("Investor {}: locked={}, payout={}", i, locked_amount, payout);
-
-        // Apply minimum payout threshold
-        if payout < policy.min_payout_lamports {
-            msg!("Payout {} below minimum {}, adding to dust",
-                 payout, policy.min_payout_lamports);
+        msg!(
+            "Investor {}: locked={}, payout={}",
+            i,
+            locked_amount,
+            payout
+        );
 
-            // Carry forward as dust
+        // Sub-minimum shares accrue to the investor until the balance reaches the minimum
+        let mut accrual = accruals::load_or_create(
+            accrual_info,
+            stream,
+            &vault_id,
+            &ctx.accounts.cranker.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            ctx.program_id,
+        )?;
+        let previously_accrued = accrual.accrued;
+        let (amount_to_pay, accrued_after) =
+            accruals::settle(payout, previously_accrued, min_payout)?;
+        accrual.accrued = accrued_after;
+        accruals::save(accrual_info, &accrual)?;
+
+        if amount_to_pay == 0 {
+            msg!(
+                "Payout {} below minimum {}, accrued balance now {}",
+                payout,
+                min_payout,
+                accrued_after
+            );
+
+            // Moved out of today's pool into the investor's accrual
+            progress.daily_dust = safe_math::add(progress.daily_dust, payout)?;
             progress.carry_over_dust = safe_math::add(progress.carry_over_dust, payout)?;
+            if payout > 0 {
+                events::emit_dust_accumulated(vault_id, payout, progress.carry_over_dust)?;
+            }
             continue;
         }
 
         // Get investor ATA from remaining accounts
-        let investor_ata_info = &remaining_accounts[i * 2 + 1];
+        let investor_ata_info = &remaining_accounts[i * PAYOUT_ACCOUNTS_PER_INVESTOR + 1];
 
         // Deserialize investor token account
-        let investor_ata = Account::<TokenAccount>::try_from(investor_ata_info)
+        let investor_ata = InterfaceAccount::<TokenAccount>::try_from(investor_ata_info)
             .map_err(|_| StarInvestorFeesError::InvalidInvestorAta)?;
 
         // Validate investor ATA
@@ -331,50 +425,80 @@ pub fn handler(
             investor_ata.mint == policy.quote_mint,
             StarInvestorFeesError::InvalidInvestorAta
         );
+        require!(
+            investor_ata.owner == page_investors[i].recipient,
+            StarInvestorFeesError::InvalidInvestorAta
+        );
+        require!(
+            investor_ata.owner == investor_recipients[i],
+            StarInvestorFeesError::InvalidInvestorAta
+        );
 
         // Transfer tokens to investor
         let transfer_ctx = CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
-            Transfer {
+            TransferChecked {
                 from: ctx.accounts.treasury_ata.to_account_info(),
+                mint: ctx.accounts.quote_mint.to_account_info(),
                 to: investor_ata_info.clone(),
                 authority: ctx.accounts.position_owner_pda.to_account_info(),
             },
             signer_seeds,
         );
 
-        token::transfer(transfer_ctx, payout)?;
+        token_interface::transfer_checked(
+            transfer_ctx,
+            amount_to_pay,
+            ctx.accounts.quote_mint.decimals,
+        )?;
+
+        msg!(
+            "✓ Transferred {} to investor {} ({} previously accrued)",
+            amount_to_pay,
+            i,
+            previously_accrued
+        );
 
-        msg!("✓ Transferred {} to investor {}", payout, i);
+        // Only today's share comes out of the pool; the accrued part was already set aside
+        progress.daily_distributed_to_investors =
+            safe_math::add(progress.daily_distributed_to_investors, payout)?;
+        progress.carry_over_dust = safe_math::sub(progress.carry_over_dust, previously_accrued)?;
 
-        total_paid_this_page = safe_math::add(total_paid_this_page, payout)?;
+        total_paid_this_page = safe_math::add(total_paid_this_page, amount_to_pay)?;
         investors_paid = safe_math::add(investors_paid as u64, 1)? as u16;
     }
 
-    msg!("Page complete: {} paid to {} investors", total_paid_this_page, investors_paid);
+    msg!(
+        "Page complete: {} paid to {} investors",
+        total_paid_this_page,
+        investors_paid
+    );
 
-    // Update progress
-    progress.daily_distributed_to_investors = safe_math::add(
-        progress.daily_distributed_to_investors,
-        total_paid_this_page
-    )?;
+    // Pages can never pay out or withhold more than the day's investor pool
+    require!(
+        safe_math::add(progress.daily_distributed_to_investors, progress.daily_dust)?
+            <= progress.investor_fee_pool,
+        StarInvestorFeesError::InconsistentDistributionState
+    );
 
     progress.current_page = safe_math::add(progress.current_page as u64, 1)? as u16;
 
-    // Emit event
-    events::emit_payout_page(
-        vault_id,
-        page_number,
-        investors_paid,
-        total_paid_this_page,
+    // Whatever the pool has not paid out yet (dust included) must still be in the treasury
+    ctx.accounts.treasury_ata.reload()?;
+    validation::validate_treasury_covers(
+        ctx.accounts.treasury_ata.amount,
+        progress.outstanding_investor_fees(),
     )?;
 
+    // Emit event
+    events::emit_payout_page(vault_id, page_number, investors_paid, total_paid_this_page)?;
+
     // Log summary
     logging::log_distribution_summary(
         page_number,
         investors_paid,
         total_paid_this_page,
-        total_locked_this_page,
+        total_locked,
     );
 
     msg!("=== Distribution Page {} Complete ===", page_number);
@@ -382,36 +506,67 @@ pub fn handler(
     Ok(())
 }
 
+/// Meteora DAMM v2 `claim_position_fee` discriminator (from idl)
+const CLAIM_POSITION_FEE_DISCRIMINATOR: [u8; 8] = [180, 38, 154, 17, 133, 33, 162, 211];
+
 /// Claim fees from the honorary position via CP-AMM
-fn claim_fees_from_position(ctx: &Context<DistributeFees>) -> Result<u64> {
-    msg!("Claiming fees from position: {}", ctx.accounts.position.key());
+///
+/// Returns `(claimed_quote, claimed_base)` measured as the balance change of
+/// the quote treasury and the base treasury across the claim CPI.
+fn claim_fees_from_position(
+    ctx: &mut Context<DistributeFees>,
+    vault_id: [u8; 32],
+) -> Result<(u64, u64)> {
+    msg!(
+        "Claiming fees from position: {}",
+        ctx.accounts.position.key()
+    );
+
+    // The pool's fee mode could have changed since the position was created
+    DammV2Pool::load(&ctx.accounts.pool, &ctx.accounts.cp_amm_program.key())?.validate_quote_only(
+        &ctx.accounts.base_mint.key(),
+        &ctx.accounts.quote_mint.key(),
+        &ctx.accounts.pool_base_vault.key(),
+        &ctx.accounts.pool_quote_vault.key(),
+    )?;
 
-    // Get treasury balance before claim
+    // Get treasury balances before claim
     let balance_before = ctx.accounts.treasury_ata.amount;
+    let base_balance_before = ctx.accounts.base_treasury.amount;
 
     msg!("Treasury balance before claim: {}", balance_before);
+    msg!(
+        "Base treasury balance before claim: {}",
+        base_balance_before
+    );
 
     // Claim fees via CPI to Meteora's claim_position_fee instruction
-    let vault_id = ctx.accounts.progress.load()?.vault_id;
     claim_position_fee_cpi(ctx, vault_id)?;
 
-    // After CPI, reload account to get new balance
+    // After CPI, reload accounts to get new balances
     ctx.accounts.treasury_ata.reload()?;
+    ctx.accounts.base_treasury.reload()?;
     let balance_after = ctx.accounts.treasury_ata.amount;
+    let base_balance_after = ctx.accounts.base_treasury.amount;
 
     msg!("Treasury balance after claim: {}", balance_after);
+    msg!("Base treasury balance after claim: {}", base_balance_after);
 
-    // Calculate claimed amount
+    // Calculate claimed amounts
     let claimed = balance_after.saturating_sub(balance_before);
-
-    require!(claimed > 0, StarInvestorFeesError::ZeroFeeClaim);
+    let claimed_base = base_balance_after.saturating_sub(base_balance_before);
 
     logging::log_fee_claim(claimed, balance_after);
+    msg!("Base fees claimed: {}", claimed_base);
 
-    Ok(claimed)
+    Ok((claimed, claimed_base))
 }
 
 /// Claim position fees via Meteora CPI
+///
+/// With `collect_fee_mode = 1` the pool only collects fees in token B, so the
+/// quote mint is token B and the base mint is token A. Base-side fees (if any)
+/// land in the base treasury, never in the quote treasury.
 fn claim_position_fee_cpi(ctx: &Context<DistributeFees>, vault_id: [u8; 32]) -> Result<()> {
     msg!("Executing claim_position_fee CPI to Meteora");
 
@@ -425,35 +580,25 @@ fn claim_position_fee_cpi(ctx: &Context<DistributeFees>, vault_id: [u8; 32]) ->
     ];
     let signer_seeds = &[&seeds[..]];
 
-    // TODO: Add Meteora claim_position_fee instruction discriminator
-    let discriminator: [u8; 8] = [0, 0, 0, 0, 0, 0, 0, 0]; // Placeholder
-
-    let instruction_data = discriminator.to_vec();
-
-    // Get pool authority (Meteora constant PDA)
-    // For Meteora, pool_authority is typically a constant PDA
-    let pool_authority = anchor_lang::solana_program::pubkey!("3XXEuz5z6C6oPbM54CXZqy6kHKhsTi8HjYYt4BPcBWWM");
-
-    // Determine which token is A and which is B based on pool configuration
-    // For our case: token_a = quote, token_b = base (or vice versa)
-    let token_a_mint = ctx.accounts.policy.load()?.quote_mint;
-    let token_b_mint = ctx.accounts.pool_base_vault.mint; // Get base mint from vault
+    let instruction_data = CLAIM_POSITION_FEE_DISCRIMINATOR.to_vec();
 
     // Build account metas for Meteora's claim_position_fee instruction
     let account_metas = vec![
-        AccountMeta::new_readonly(pool_authority, false),                       // pool_authority
-        AccountMeta::new_readonly(ctx.accounts.pool.key(), false),              // pool
-        AccountMeta::new(ctx.accounts.position.key(), false),                   // position
-        AccountMeta::new(ctx.accounts.treasury_ata.key(), false),               // token_a_account (receives quote fees)
-        AccountMeta::new(ctx.accounts.treasury_ata.key(), false),               // token_b_account (also to treasury)
-        AccountMeta::new(ctx.accounts.pool_quote_vault.key(), false),           // token_a_vault
-        AccountMeta::new(ctx.accounts.pool_base_vault.key(), false),            // token_b_vault
-        AccountMeta::new_readonly(token_a_mint, false),                         // token_a_mint
-        AccountMeta::new_readonly(token_b_mint, false),                         // token_b_mint
-        AccountMeta::new_readonly(ctx.accounts.position_nft_account.key(), false), // position_nft_account (needs to be passed)
+        AccountMeta::new_readonly(ctx.accounts.pool_authority.key(), false), // pool_authority
+        AccountMeta::new_readonly(ctx.accounts.pool.key(), false),           // pool
+        AccountMeta::new(ctx.accounts.position.key(), false),                // position
+        AccountMeta::new(ctx.accounts.base_treasury.key(), false),           // token_a_account
+        AccountMeta::new(ctx.accounts.treasury_ata.key(), false),            // token_b_account
+        AccountMeta::new(ctx.accounts.pool_base_vault.key(), false),         // token_a_vault
+        AccountMeta::new(ctx.accounts.pool_quote_vault.key(), false),        // token_b_vault
+        AccountMeta::new_readonly(ctx.accounts.base_mint.key(), false),      // token_a_mint
+        AccountMeta::new_readonly(ctx.accounts.quote_mint.key(), false),     // token_b_mint
+        AccountMeta::new_readonly(ctx.accounts.position_nft_account.key(), false), // position_nft_account
         AccountMeta::new_readonly(ctx.accounts.position_owner_pda.key(), true), // owner (signer via PDA)
-        AccountMeta::new_readonly(ctx.accounts.token_program.key(), false),     // token_a_program
+        AccountMeta::new_readonly(ctx.accounts.base_token_program.key(), false), // token_a_program
         AccountMeta::new_readonly(ctx.accounts.token_program.key(), false),     // token_b_program
+        AccountMeta::new_readonly(ctx.accounts.event_authority.key(), false),   // event_authority
+        AccountMeta::new_readonly(ctx.accounts.cp_amm_program.key(), false),    // program
     ];
 
     // Create the instruction
@@ -464,17 +609,24 @@ fn claim_position_fee_cpi(ctx: &Context<DistributeFees>, vault_id: [u8; 32]) ->
     };
 
     // Invoke the CPI with PDA signer
-    solana_program::program::invoke_signed(
+    invoke_signed(
         &instruction,
         &[
-            // Note: Need to pass all accounts including pool_authority
+            ctx.accounts.pool_authority.to_account_info(),
             ctx.accounts.pool.to_account_info(),
             ctx.accounts.position.to_account_info(),
+            ctx.accounts.base_treasury.to_account_info(),
             ctx.accounts.treasury_ata.to_account_info(),
-            ctx.accounts.pool_quote_vault.to_account_info(),
             ctx.accounts.pool_base_vault.to_account_info(),
+            ctx.accounts.pool_quote_vault.to_account_info(),
+            ctx.accounts.base_mint.to_account_info(),
+            ctx.accounts.quote_mint.to_account_info(),
+            ctx.accounts.position_nft_account.to_account_info(),
             ctx.accounts.position_owner_pda.to_account_info(),
+            ctx.accounts.base_token_program.to_account_info(),
             ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.event_authority.to_account_info(),
+            ctx.accounts.cp_amm_program.to_account_info(),
         ],
         signer_seeds,
     )?;
@@ -485,107 +637,286 @@ fn claim_position_fee_cpi(ctx: &Context<DistributeFees>, vault_id: [u8; 32]) ->
 }
 
 /// Validate that only quote fees were claimed (no base fees)
-fn validate_quote_only_claim(ctx: &Context<DistributeFees>, claimed_quote: u64) -> Result<()> {
-    // In production, would check that:
-    // 1. No base tokens appeared in treasury
-    // 2. Position only accrued quote fees
-    // 3. Pool state confirms quote-only fee generation
-
-    // For now, basic validation
-    require!(claimed_quote > 0, StarInvestorFeesError::ZeroFeeClaim);
-
-    // Check that base vault balance didn't change (no base fees)
-    // In actual implementation, would track base vault balance before/after
-    // and fail if any base tokens were received
-
-    msg!("Quote-only validation passed: {} quote tokens claimed", claimed_quote);
+///
+/// Base-side fees land in the base treasury, so any increase there means the
+/// position accrued base fees and the whole claim is rejected.
+fn validate_quote_only_claim(claimed_quote: u64, claimed_base: u64) -> Result<()> {
+    require!(claimed_base == 0, StarInvestorFeesError::BaseFeesDetected);
+
+    msg!(
+        "Quote-only validation passed: {} quote tokens claimed",
+        claimed_quote
+    );
 
     Ok(())
 }
 
-/// Parse Streamflow stream account data
-fn parse_streamflow_stream(account_info: &AccountInfo) -> Result<StreamflowStream> {
-    msg!("Parsing Streamflow stream: {}", account_info.key);
-
-    // Validate account
-    require!(
-        account_info.owner == &anchor_lang::solana_program::pubkey!("strmRqUCoQUgGUan5YhzUZa6KqdzwX5L6FpUTNYXr6vk"),
-        StarInvestorFeesError::InvalidStreamflowAccount
-    );
-
-    let data = account_info.try_borrow_data()?;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{LockSourceKind, RegistryEntry};
+    use crate::utils::merkle;
 
-    // Validate minimum length
-    require!(
-        data.len() >= 48,
-        StarInvestorFeesError::StreamflowDeserializationFailed
-    );
+    #[test]
+    fn test_distribute_account_metas() {
+        let accounts = crate::accounts::DistributeFees {
+            cranker: Pubkey::new_unique(),
+            policy: Pubkey::new_unique(),
+            progress: Pubkey::new_unique(),
+            registry_page: None,
+            position_owner_pda: Pubkey::new_unique(),
+            position: Pubkey::new_unique(),
+            treasury_ata: Pubkey::new_unique(),
+            base_treasury: Pubkey::new_unique(),
+            pool: Pubkey::new_unique(),
+            pool_quote_vault: Pubkey::new_unique(),
+            pool_base_vault: Pubkey::new_unique(),
+            quote_mint: Pubkey::new_unique(),
+            base_mint: Pubkey::new_unique(),
+            pool_authority: Pubkey::new_unique(),
+            event_authority: Pubkey::new_unique(),
+            position_nft_account: Pubkey::new_unique(),
+            cp_amm_program: Pubkey::new_unique(),
+            token_program: Pubkey::new_unique(),
+            base_token_program: Pubkey::new_unique(),
+            system_program: Pubkey::new_unique(),
+        };
+        let metas = accounts.to_account_metas(None);
+        let is_writable = |key: Pubkey| {
+            metas
+                .iter()
+                .find(|meta| meta.pubkey == key)
+                .unwrap()
+                .is_writable
+        };
+
+        // A full page fits in one transaction: named accounts, the program, and each investor
+        assert!(
+            metas.len() + 1 + PAYOUT_ACCOUNTS_PER_INVESTOR * MAX_INVESTORS_PER_PAGE
+                <= MAX_TX_ACCOUNT_LOCKS
+        );
 
-    // Parse Streamflow stream data structure
-    // Note: This is a simplified version. Actual Streamflow format may differ.
-    // In production, would use Streamflow SDK for proper deserialization.
+        // claim_position_fee writes these, so clients must send them writable
+        for key in [
+            accounts.position,
+            accounts.treasury_ata,
+            accounts.base_treasury,
+            accounts.pool_base_vault,
+            accounts.pool_quote_vault,
+        ] {
+            assert!(is_writable(key));
+        }
+    }
 
-    // Skip discriminator (first 8 bytes) and parse fields
-    let amount = u64::from_le_bytes(
-        data[8..16].try_into()
-            .map_err(|_| StarInvestorFeesError::StreamflowDeserializationFailed)?
-    );
+    #[test]
+    fn test_claim_position_fee_discriminator() {
+        let hash = anchor_lang::solana_program::hash::hash(b"global:claim_position_fee");
+        assert_eq!(CLAIM_POSITION_FEE_DISCRIMINATOR, hash.to_bytes()[..8]);
+    }
 
-    let withdrawn = u64::from_le_bytes(
-        data[16..24].try_into()
-            .map_err(|_| StarInvestorFeesError::StreamflowDeserializationFailed)?
-    );
+    #[test]
+    fn test_quote_only_claim_rejects_base_fees() {
+        assert!(validate_quote_only_claim(1_000, 0).is_ok());
+        assert_eq!(
+            validate_quote_only_claim(1_000, 1).unwrap_err(),
+            StarInvestorFeesError::BaseFeesDetected.into()
+        );
+        assert!(validate_quote_only_claim(0, 0).is_ok());
+    }
 
-    let start_time = u64::from_le_bytes(
-        data[24..32].try_into()
-            .map_err(|_| StarInvestorFeesError::StreamflowDeserializationFailed)?
-    );
+    fn policy() -> PolicyConfig {
+        PolicyConfig {
+            vault_id: [1u8; 32],
+            authority: Pubkey::new_unique(),
+            pending_authority: None,
+            creator: Pubkey::new_unique(),
+            investor_fee_share_bps: 5_000,
+            daily_cap_lamports: None,
+            min_payout_lamports: 1_000,
+            y0_total_allocation: 1_000_000,
+            quote_mint: Pubkey::new_unique(),
+            base_mint: Pubkey::new_unique(),
+            pool: Pubkey::new_unique(),
+            position: Pubkey::new_unique(),
+            lock_source: LockSourceKind::Streamflow,
+            registry_pages: 1,
+            finalize_grace_seconds: DEFAULT_FINALIZE_GRACE_SECONDS,
+            investor_root: [0u8; 32],
+            investor_count: 0,
+            merkle_page_size: 0,
+            paused: false,
+            bump: 255,
+        }
+    }
 
-    let end_time = u64::from_le_bytes(
-        data[32..40].try_into()
-            .map_err(|_| StarInvestorFeesError::StreamflowDeserializationFailed)?
-    );
+    #[test]
+    fn test_remaining_accounts_must_be_grouped() {
+        let count = PAYOUT_ACCOUNTS_PER_INVESTOR * (MAX_INVESTORS_PER_PAGE + 1);
+        let keys: Vec<Pubkey> = (0..count).map(|_| Pubkey::new_unique()).collect();
+        let owner = Pubkey::default();
+        let mut lamports = vec![0u64; count];
+        let mut data = vec![Vec::<u8>::new(); count];
+        let accounts: Vec<AccountInfo> = keys
+            .iter()
+            .zip(lamports.iter_mut())
+            .zip(data.iter_mut())
+            .map(|((key, lamports), data)| {
+                AccountInfo::new(key, false, true, lamports, data, &owner, false, 0)
+            })
+            .collect();
+
+        // Whole (stream, ATA, receipt, accrual) groups yield one stream per investor
+        let streams =
+            investors::page_streams(&accounts[..8], PAYOUT_ACCOUNTS_PER_INVESTOR).unwrap();
+        assert_eq!(streams, vec![keys[0], keys[4]]);
+        assert!(investors::page_streams(&[], PAYOUT_ACCOUNTS_PER_INVESTOR)
+            .unwrap()
+            .is_empty());
+
+        // Partial groups are rejected
+        for len in [1, 3, 6, 9] {
+            assert_eq!(
+                investors::page_streams(&accounts[..len], PAYOUT_ACCOUNTS_PER_INVESTOR)
+                    .unwrap_err(),
+                StarInvestorFeesError::InvalidRemainingAccounts.into()
+            );
+        }
 
-    let cliff = u64::from_le_bytes(
-        data[40..48].try_into()
-            .map_err(|_| StarInvestorFeesError::StreamflowDeserializationFailed)?
-    );
+        // One investor over the page limit
+        assert_eq!(
+            investors::page_streams(&accounts, PAYOUT_ACCOUNTS_PER_INVESTOR).unwrap_err(),
+            StarInvestorFeesError::TooManyInvestorsPerPage.into()
+        );
+    }
 
-    msg!("Stream parsed: amount={}, withdrawn={}, start={}, end={}",
-         amount, withdrawn, start_time, end_time);
+    #[test]
+    fn test_registry_page_must_match_streams() {
+        let policy = policy();
+        let entries: Vec<RegistryEntry> = (0..2)
+            .map(|_| RegistryEntry {
+                stream: Pubkey::new_unique(),
+                recipient: Pubkey::new_unique(),
+            })
+            .collect();
+        let registry_page = InvestorRegistryPage {
+            vault_id: [1u8; 32],
+            page_index: 0,
+            entries: entries.clone(),
+            bump: 255,
+        };
+        let streams = [entries[0].stream, entries[1].stream];
+
+        let page_investors =
+            investors::resolve_page(&policy, Some(&registry_page), 0, &streams, &[]).unwrap();
+        assert_eq!(page_investors.len(), 2);
+        assert_eq!(page_investors[1].recipient, entries[1].recipient);
+        assert_eq!(page_investors[1].weight_cap, u64::MAX);
+
+        // Fewer streams than registered investors
+        assert_eq!(
+            investors::resolve_page(&policy, Some(&registry_page), 0, &streams[..1], &[])
+                .unwrap_err(),
+            StarInvestorFeesError::InconsistentInvestorData.into()
+        );
 
-    Ok(StreamflowStream {
-        amount,
-        withdrawn,
-        start_time,
-        end_time,
-        cliff,
-    })
-}
+        // Registered streams out of order
+        assert_eq!(
+            investors::resolve_page(
+                &policy,
+                Some(&registry_page),
+                0,
+                &[streams[1], streams[0]],
+                &[]
+            )
+            .unwrap_err(),
+            StarInvestorFeesError::InvestorNotRegistered.into()
+        );
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        // Proofs are not accepted in registry mode, and the page is required
+        let proof = InvestorProof {
+            investor_owner: entries[0].recipient,
+            weight_cap: 1,
+            proof: vec![],
+        };
+        assert_eq!(
+            investors::resolve_page(&policy, Some(&registry_page), 0, &streams, &[proof])
+                .unwrap_err(),
+            StarInvestorFeesError::InconsistentInvestorData.into()
+        );
+        assert_eq!(
+            investors::resolve_page(&policy, None, 0, &streams, &[]).unwrap_err(),
+            StarInvestorFeesError::InvestorNotRegistered.into()
+        );
+    }
 
     #[test]
-    fn test_investor_count_validation() {
-        // Valid: 0-20 investors
-        assert!(20 <= MAX_INVESTORS_PER_PAGE);
-        assert!(0 <= MAX_INVESTORS_PER_PAGE);
+    fn test_merkle_page_must_match_streams() {
+        let streams: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let owner = Pubkey::new_unique();
+        let leaves: Vec<[u8; 32]> = streams
+            .iter()
+            .map(|stream| merkle::leaf(stream, &owner, 1_000))
+            .collect();
+        let ab = merkle::node(&leaves[0], &leaves[1]);
+
+        let mut policy = policy();
+        policy.investor_root = merkle::node(&ab, &leaves[2]);
+        policy.investor_count = 3;
+        policy.merkle_page_size = 2;
+
+        let proof = |siblings: Vec<[u8; 32]>| InvestorProof {
+            investor_owner: owner,
+            weight_cap: 1_000,
+            proof: siblings,
+        };
+
+        // Last page holds the single leftover investor
+        let page_investors =
+            investors::resolve_page(&policy, None, 1, &streams[2..], &[proof(vec![ab])]).unwrap();
+        assert_eq!(page_investors[0].weight_cap, 1_000);
+
+        // Too many streams for the page
+        assert_eq!(
+            investors::resolve_page(
+                &policy,
+                None,
+                1,
+                &streams[1..],
+                &[proof(vec![leaves[0], leaves[2]]), proof(vec![ab])]
+            )
+            .unwrap_err(),
+            StarInvestorFeesError::InconsistentInvestorData.into()
+        );
+
+        // One proof short of the page's streams
+        assert_eq!(
+            investors::resolve_page(
+                &policy,
+                None,
+                0,
+                &streams[..2],
+                &[proof(vec![leaves[1], leaves[2]])]
+            )
+            .unwrap_err(),
+            StarInvestorFeesError::InconsistentInvestorData.into()
+        );
     }
 
     #[test]
-    fn test_remaining_accounts_must_be_even() {
-        // Valid: even number (pairs)
-        let valid_counts = [0, 2, 4, 20, 40];
-        for count in valid_counts {
-            assert_eq!(count % 2, 0);
-        }
+    fn test_payout_weight_cannot_exceed_locked_total() {
+        assert_eq!(
+            fee_calc::calculate_proportional_payout(1_000, 250, 1_000).unwrap(),
+            250
+        );
+        assert_eq!(
+            fee_calc::calculate_proportional_payout(1_000, 250, 0).unwrap(),
+            0
+        );
 
-        // Invalid: odd number
-        let invalid_counts = [1, 3, 5, 21];
-        for count in invalid_counts {
-            assert_ne!(count % 2, 0);
-        }
+        // A weight the snapshot total does not cover would pay out more than the pool
+        assert_eq!(
+            fee_calc::calculate_proportional_payout(1_000, 1_001, 1_000).unwrap_err(),
+            StarInvestorFeesError::InconsistentDistributionState.into()
+        );
     }
 }
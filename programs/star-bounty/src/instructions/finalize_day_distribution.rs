@@ -1,8 +1,9 @@
 use crate::constants::*;
 use crate::errors::StarInvestorFeesError;
 use crate::state::{DistributionProgress, PolicyConfig};
+use crate::utils::{safe_math, validation};
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 
 #[derive(Accounts)]
 #[instruction(vault_id: [u8; 32])]
@@ -38,10 +39,11 @@ pub struct FinalizeDayDistribution<'info> {
         mut,
         seeds = [TREASURY_SEED, vault_id.as_ref()],
         bump,
-        token::mint = policy.quote_mint,
-        token::authority = position_owner_pda
+        token::mint = quote_mint,
+        token::authority = position_owner_pda,
+        token::token_program = token_program
     )]
-    pub treasury_ata: Account<'info, TokenAccount>,
+    pub treasury_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// Creator's quote ATA (receives remainder)
     #[account(
@@ -49,9 +51,17 @@ pub struct FinalizeDayDistribution<'info> {
         constraint = creator_ata.mint == policy.quote_mint,
         constraint = creator_ata.owner == policy.creator
     )]
-    pub creator_ata: Account<'info, TokenAccount>,
+    pub creator_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    pub token_program: Program<'info, Token>,
+    /// Quote mint
+    #[account(
+        mint::token_program = token_program,
+        constraint = quote_mint.key() == policy.quote_mint @ StarInvestorFeesError::InvalidQuoteMint
+    )]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Token program owning the quote mint (legacy SPL Token or Token-2022)
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 pub fn handler(ctx: Context<FinalizeDayDistribution>, vault_id: [u8; 32]) -> Result<()> {
@@ -64,13 +74,61 @@ pub fn handler(ctx: Context<FinalizeDayDistribution>, vault_id: [u8; 32]) -> Res
         StarInvestorFeesError::DayAlreadyFinalized
     );
 
-    // Calculate remainder to send to creator
-    let total_claimed = progress.daily_claimed_amount + progress.carry_over_dust;
+    require!(
+        progress.is_day_in_progress(),
+        StarInvestorFeesError::CannotFinalizeBeforeDistribution
+    );
+
+    // Every investor page must be paid, unless the crank stalled past the grace window
+    let payout_complete = progress.is_payout_complete();
+    if !payout_complete {
+        let grace_ends = progress
+            .current_day_start
+            .checked_add(ctx.accounts.policy.finalize_grace_seconds)
+            .ok_or(StarInvestorFeesError::ArithmeticOverflow)?;
+        require!(
+            current_ts >= grace_ends,
+            StarInvestorFeesError::DayNotReadyForFinalization
+        );
+    }
+
+    // Today's claim plus the fees carried in funded today's investor pool
+    let total_available = safe_math::add(
+        progress.daily_claimed_amount,
+        progress.owed_from_previous_days(),
+    )?;
     let distributed_to_investors = progress.daily_distributed_to_investors;
+    let dust = progress.daily_dust;
+
+    // Pending pages, or a day with no one to assign to, keep the investor pool
+    // instead of folding it into the remainder
+    let unpaid_investor_fees = progress.investor_fees_to_carry();
+
+    // The creator only receives what investors are not owed
+    let owed_to_investors = safe_math::add(
+        safe_math::add(distributed_to_investors, dust)?,
+        unpaid_investor_fees,
+    )?;
+    let remainder = safe_math::sub(total_available, owed_to_investors)?;
+
+    // Unpaid shares stay in the treasury for the next day's pool; accrued dust
+    // stays in investors' accruals
+    progress.carried_investor_fees = unpaid_investor_fees;
+    if unpaid_investor_fees > 0 {
+        msg!(
+            "{} pages pending, carrying {} to investors",
+            progress.total_pages - progress.current_page,
+            unpaid_investor_fees
+        );
 
-    let remainder = total_claimed
-        .checked_sub(distributed_to_investors)
-        .ok_or(StarInvestorFeesError::ArithmeticUnderflow)?;
+        emit!(UnpaidInvestorFeesCarried {
+            vault_id,
+            pages_pending: progress.total_pages - progress.current_page,
+            amount_carried: unpaid_investor_fees,
+            day_start: progress.current_day_start,
+            timestamp: current_ts,
+        });
+    }
 
     // Transfer remainder to creator if > 0
     if remainder > 0 {
@@ -85,15 +143,20 @@ pub fn handler(ctx: Context<FinalizeDayDistribution>, vault_id: [u8; 32]) -> Res
 
         let transfer_ctx = CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
-            Transfer {
+            TransferChecked {
                 from: ctx.accounts.treasury_ata.to_account_info(),
+                mint: ctx.accounts.quote_mint.to_account_info(),
                 to: ctx.accounts.creator_ata.to_account_info(),
                 authority: ctx.accounts.position_owner_pda.to_account_info(),
             },
             signer_seeds,
         );
 
-        token::transfer(transfer_ctx, remainder)?;
+        token_interface::transfer_checked(
+            transfer_ctx,
+            remainder,
+            ctx.accounts.quote_mint.decimals,
+        )?;
 
         progress.daily_distributed_to_creator = remainder;
     }
@@ -101,6 +164,13 @@ pub fn handler(ctx: Context<FinalizeDayDistribution>, vault_id: [u8; 32]) -> Res
     // Mark day as finalized
     progress.day_finalized = true;
 
+    // The treasury must still cover every carried fee and dust amount
+    ctx.accounts.treasury_ata.reload()?;
+    validation::validate_treasury_covers(
+        ctx.accounts.treasury_ata.amount,
+        progress.outstanding_investor_fees(),
+    )?;
+
     // Emit event
     emit!(CreatorPayoutDayClosed {
         vault_id,
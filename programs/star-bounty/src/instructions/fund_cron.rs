@@ -0,0 +1,59 @@
+use crate::constants::*;
+use crate::errors::StarInvestorFeesError;
+use crate::state::CronConfig;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+
+#[derive(Accounts)]
+#[instruction(vault_id: [u8; 32])]
+pub struct FundCron<'info> {
+    /// Anyone may top up a vault's cron job
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(
+        seeds = [CRON_SEED, vault_id.as_ref()],
+        bump = cron_config.bump
+    )]
+    pub cron_config: Account<'info, CronConfig>,
+
+    /// Cron job paying its tasks' crank fees
+    /// CHECK: Must be the vault's registered cron job
+    #[account(
+        mut,
+        constraint = cron_job.key() == cron_config.cron_job @ StarInvestorFeesError::CronJobMismatch
+    )]
+    pub cron_job: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<FundCron>, vault_id: [u8; 32], lamports: u64) -> Result<()> {
+    require!(
+        lamports > 0,
+        StarInvestorFeesError::TuktukInsufficientFunding
+    );
+
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.funder.to_account_info(),
+                to: ctx.accounts.cron_job.to_account_info(),
+            },
+        ),
+        lamports,
+    )?;
+
+    msg!("Cron job funded with {} lamports", lamports);
+
+    emit!(CronFunded {
+        vault_id,
+        cron_job: ctx.accounts.cron_job.key(),
+        funder: ctx.accounts.funder.key(),
+        lamports,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
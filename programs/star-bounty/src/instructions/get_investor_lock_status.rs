@@ -0,0 +1,30 @@
+use crate::constants::*;
+use crate::state::{InvestorLock, InvestorLockStatus};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(vault_id: [u8; 32], recipient: Pubkey)]
+pub struct GetInvestorLockStatus<'info> {
+    #[account(
+        seeds = [INVESTOR_LOCK_SEED, vault_id.as_ref(), recipient.as_ref()],
+        bump = investor_lock.bump
+    )]
+    pub investor_lock: Account<'info, InvestorLock>,
+}
+
+pub fn handler(
+    ctx: Context<GetInvestorLockStatus>,
+    _vault_id: [u8; 32],
+    _recipient: Pubkey,
+) -> Result<InvestorLockStatus> {
+    let current_ts = Clock::get()?.unix_timestamp;
+    let status = ctx.accounts.investor_lock.status(current_ts);
+
+    msg!(
+        "Lock status: locked={}, available={}",
+        status.locked_amount,
+        status.available_to_withdraw
+    );
+
+    Ok(status)
+}
@@ -1,12 +1,12 @@
 use crate::constants::*;
+use crate::damm_v2::DammV2Pool;
 use crate::errors::StarInvestorFeesError;
+use crate::external_programs::CP_AMM_PROGRAM_ID;
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::instruction::Instruction;
 use anchor_lang::solana_program::program::invoke_signed;
 use anchor_spl::token_2022::Token2022;
-use anchor_spl::token_interface::{
-    token_metadata_initialize, Mint, TokenAccount, TokenMetadataInitialize,
-};
+use anchor_spl::token_interface::{Mint, TokenAccount};
 
 #[derive(Accounts)]
 #[instruction(vault_id: [u8; 32])]
@@ -44,7 +44,12 @@ pub struct InitializeHonoraryPosition<'info> {
     pub position: UncheckedAccount<'info>,
 
     /// Pool authority (Meteora PDA)
-    /// CHECK: Meteora pool authority PDA
+    /// CHECK: Derived from the CP-AMM program ID
+    #[account(
+        seeds = [POOL_AUTHORITY_SEED],
+        bump,
+        seeds::program = meteora_program.key()
+    )]
     pub pool_authority: UncheckedAccount<'info>,
 
     /// Quote mint (must be verified to match pool)
@@ -68,7 +73,7 @@ pub struct InitializeHonoraryPosition<'info> {
     /// Meteora program
     /// CHECK: Program ID validated
     #[account(
-        constraint = meteora_program.key() == anchor_lang::solana_program::pubkey!("cpamdpZCGKUy5JxQXB4dcpGPiikHawvSWAd6mEn1sGG")
+        constraint = meteora_program.key() == CP_AMM_PROGRAM_ID
             @ StarInvestorFeesError::InvalidCpAmmProgram
     )]
     pub meteora_program: UncheckedAccount<'info>,
@@ -79,7 +84,13 @@ pub struct InitializeHonoraryPosition<'info> {
     /// System program
     pub system_program: Program<'info, System>,
 
-    /// CHECK: Will check later
+    /// CP-AMM event authority
+    /// CHECK: Derived from the CP-AMM program ID
+    #[account(
+        seeds = [EVENT_AUTHORITY_SEED],
+        bump,
+        seeds::program = meteora_program.key()
+    )]
     pub event_authority: UncheckedAccount<'info>,
 }
 
@@ -111,16 +122,6 @@ pub fn handler(ctx: Context<InitializeHonoraryPosition>, vault_id: [u8; 32]) ->
         ctx.accounts.position_nft_mint.key()
     );
 
-    // Check mint to see if fee collection is quote only
-    // Checking if 'collectFeeMode: 1' on the pool struct
-    let collect_fee_mode = {
-        let data = ctx.accounts.pool.data.borrow();
-        data[484] // u8 value at offset 476 + 8(Discriminator)
-    };
-
-    require!(collect_fee_mode == 1, StarInvestorFeesError::InvalidFeeMode);
-
-    msg!("collect_fee_mode = {}", collect_fee_mode);
     // Create position through Meteora via CPI
     create_meteora_position(&ctx, vault_id)?;
 
@@ -164,6 +165,17 @@ fn validate_pool_configuration(ctx: &Context<InitializeHonoraryPosition>) -> Res
         StarInvestorFeesError::InvalidPoolTokenOrder
     );
 
+    // The pool must trade base/quote as token A/B and collect fees in the quote token only
+    let pool = DammV2Pool::load(&ctx.accounts.pool, &ctx.accounts.meteora_program.key())?;
+    pool.validate_quote_only(
+        &ctx.accounts.base_mint.key(),
+        &ctx.accounts.quote_mint.key(),
+        &ctx.accounts.pool_base_vault.key(),
+        &ctx.accounts.pool_quote_vault.key(),
+    )?;
+
+    msg!("collect_fee_mode = {}", pool.collect_fee_mode());
+
     msg!("Pool configuration validated");
     Ok(())
 }
@@ -1,6 +1,8 @@
 use crate::constants::*;
+use crate::damm_v2::DammV2Pool;
 use crate::errors::StarInvestorFeesError;
-use crate::state::{DistributionProgress, PolicyConfig};
+use crate::external_programs::CP_AMM_PROGRAM_ID;
+use crate::state::{DistributionProgress, LockSourceKind, PolicyConfig};
 use anchor_lang::prelude::*;
 use anchor_spl::token_2022::Token2022;
 use anchor_spl::token_interface::Mint;
@@ -38,8 +40,14 @@ pub struct InitializePolicy<'info> {
     /// Quote mint
     pub quote_mint: InterfaceAccount<'info, Mint>,
 
+    /// Base (project) mint vested by investor streams
+    #[account(
+        constraint = base_mint.key() != quote_mint.key() @ StarInvestorFeesError::InvalidPoolTokenOrder
+    )]
+    pub base_mint: InterfaceAccount<'info, Mint>,
+
     /// Pool address
-    /// CHECK: Stored in policy for validation
+    /// CHECK: Owner and token mints validated in handler
     pub pool: UncheckedAccount<'info>,
 
     /// Honorary position
@@ -59,6 +67,7 @@ pub fn handler(
     daily_cap_lamports: Option<u64>,
     min_payout_lamports: u64,
     y0_total_allocation: u64,
+    lock_source: LockSourceKind,
 ) -> Result<()> {
     // Validate fee share
     require!(
@@ -66,18 +75,39 @@ pub fn handler(
         StarInvestorFeesError::InvalidFeeShareBps
     );
 
+    // Streams vest the pool's token A, so the base mint must be token A
+    let pool = DammV2Pool::load(&ctx.accounts.pool, &CP_AMM_PROGRAM_ID)?;
+    require_keys_eq!(
+        pool.token_a_mint(),
+        ctx.accounts.base_mint.key(),
+        StarInvestorFeesError::InvalidPoolTokenOrder
+    );
+    require_keys_eq!(
+        pool.token_b_mint(),
+        ctx.accounts.quote_mint.key(),
+        StarInvestorFeesError::InvalidQuoteMint
+    );
+
     // Initialize policy
     let policy = &mut ctx.accounts.policy;
     policy.vault_id = vault_id;
     policy.authority = ctx.accounts.authority.key();
+    policy.pending_authority = None;
     policy.creator = ctx.accounts.creator.key();
     policy.investor_fee_share_bps = investor_fee_share_bps;
     policy.daily_cap_lamports = daily_cap_lamports;
     policy.min_payout_lamports = min_payout_lamports;
     policy.y0_total_allocation = y0_total_allocation;
     policy.quote_mint = ctx.accounts.quote_mint.key();
+    policy.base_mint = ctx.accounts.base_mint.key();
     policy.pool = ctx.accounts.pool.key();
     policy.position = ctx.accounts.position.key();
+    policy.lock_source = lock_source;
+    policy.registry_pages = 0;
+    policy.finalize_grace_seconds = DEFAULT_FINALIZE_GRACE_SECONDS;
+    policy.investor_root = [0u8; 32];
+    policy.investor_count = 0;
+    policy.merkle_page_size = 0;
     policy.paused = false;
     policy.bump = ctx.bumps.policy;
 
@@ -92,6 +122,11 @@ pub fn handler(
     progress.current_page = 0;
     progress.total_pages = 0;
     progress.carry_over_dust = 0;
+    progress.daily_dust = 0;
+    progress.total_locked_snapshot = 0;
+    progress.snapshot_pages_done = 0;
+    progress.investor_fee_pool = 0;
+    progress.carried_investor_fees = 0;
     progress.day_finalized = false;
     progress.bump = ctx.bumps.progress;
 
@@ -104,6 +139,7 @@ pub fn handler(
         investor_fee_share_bps,
         daily_cap_lamports,
         min_payout_lamports,
+        finalize_grace_seconds: DEFAULT_FINALIZE_GRACE_SECONDS,
         timestamp: Clock::get()?.unix_timestamp,
     });
 
@@ -0,0 +1,88 @@
+use crate::constants::*;
+use crate::errors::StarInvestorFeesError;
+use crate::state::PolicyConfig;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+#[instruction(vault_id: [u8; 32])]
+pub struct InitializeTreasury<'info> {
+    /// Policy authority paying for the treasury accounts
+    #[account(
+        mut,
+        constraint = authority.key() == policy.authority @ StarInvestorFeesError::InvalidAuthority
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [POLICY_SEED, vault_id.as_ref()],
+        bump = policy.bump
+    )]
+    pub policy: Account<'info, PolicyConfig>,
+
+    /// Position owner PDA (authority of both treasuries)
+    /// CHECK: Seeds validated
+    #[account(
+        seeds = [VAULT_SEED, vault_id.as_ref(), POSITION_OWNER_SEED],
+        bump
+    )]
+    pub position_owner_pda: UncheckedAccount<'info>,
+
+    /// Quote treasury (holds claimed fees before distribution)
+    #[account(
+        init,
+        payer = authority,
+        seeds = [TREASURY_SEED, vault_id.as_ref()],
+        bump,
+        token::mint = quote_mint,
+        token::authority = position_owner_pda,
+        token::token_program = quote_token_program
+    )]
+    pub treasury_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Base treasury (receives any base-side fees from a claim)
+    #[account(
+        init,
+        payer = authority,
+        seeds = [BASE_TREASURY_SEED, vault_id.as_ref()],
+        bump,
+        token::mint = base_mint,
+        token::authority = position_owner_pda,
+        token::token_program = base_token_program
+    )]
+    pub base_treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mint::token_program = quote_token_program,
+        constraint = quote_mint.key() == policy.quote_mint @ StarInvestorFeesError::InvalidQuoteMint
+    )]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mint::token_program = base_token_program,
+        constraint = base_mint.key() == policy.base_mint @ StarInvestorFeesError::InvalidPoolTokenOrder
+    )]
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Token program owning the quote mint
+    pub quote_token_program: Interface<'info, TokenInterface>,
+
+    /// Token program owning the base mint
+    pub base_token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeTreasury>, vault_id: [u8; 32]) -> Result<()> {
+    msg!("Quote treasury: {}", ctx.accounts.treasury_ata.key());
+    msg!("Base treasury: {}", ctx.accounts.base_treasury.key());
+
+    emit!(TreasuryInitialized {
+        vault_id,
+        quote_treasury: ctx.accounts.treasury_ata.key(),
+        base_treasury: ctx.accounts.base_treasury.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -1,15 +1,77 @@
+#![allow(ambiguous_glob_reexports)]
+
 pub mod initialize_policy;
 pub use initialize_policy::*;
 
-// pub mod distribute_fees;
-// pub use distribute_fees::*;
+pub mod initialize_treasury;
+pub use initialize_treasury::*;
+
+pub mod create_registry_page;
+pub use create_registry_page::*;
+
+pub mod add_investor;
+pub use add_investor::*;
+
+pub mod remove_investor;
+pub use remove_investor::*;
+
+pub mod set_investor_root;
+pub use set_investor_root::*;
+
+pub mod create_investor_lock;
+pub use create_investor_lock::*;
+
+pub mod withdraw_unlocked;
+pub use withdraw_unlocked::*;
+
+pub mod get_investor_lock_status;
+pub use get_investor_lock_status::*;
+
+pub mod snapshot_locked_amounts;
+pub use snapshot_locked_amounts::*;
+
+pub mod distribute_fees;
+pub use distribute_fees::*;
+
+pub mod claim_accrual;
+pub use claim_accrual::*;
 
-pub mod finalize_policy;
-pub use finalize_policy::*;
+pub mod close_investor_receipt;
+pub use close_investor_receipt::*;
+
+pub mod close_investor_accrual;
+pub use close_investor_accrual::*;
+
+pub mod update_policy;
+pub use update_policy::*;
+
+pub mod set_pause_state;
+pub use set_pause_state::*;
+
+pub mod update_creator;
+pub use update_creator::*;
+
+pub mod propose_authority;
+pub use propose_authority::*;
+
+pub mod accept_authority;
+pub use accept_authority::*;
+
+pub mod cancel_authority_proposal;
+pub use cancel_authority_proposal::*;
 
 pub mod finalize_day_distribution;
 pub use finalize_day_distribution::*;
 
+pub mod register_cron;
+pub use register_cron::*;
+
+pub mod fund_cron;
+pub use fund_cron::*;
+
+pub mod cancel_cron;
+pub use cancel_cron::*;
+
 pub mod initialize_honorary_position;
 pub use initialize_honorary_position::*;
 
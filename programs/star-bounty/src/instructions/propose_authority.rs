@@ -0,0 +1,47 @@
+use crate::constants::*;
+use crate::errors::StarInvestorFeesError;
+use crate::state::PolicyConfig;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(vault_id: [u8; 32])]
+pub struct ProposeAuthority<'info> {
+    #[account(
+        constraint = authority.key() == policy.authority @ StarInvestorFeesError::InvalidAuthority
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [POLICY_SEED, vault_id.as_ref()],
+        bump = policy.bump
+    )]
+    pub policy: Account<'info, PolicyConfig>,
+}
+
+pub fn handler(
+    ctx: Context<ProposeAuthority>,
+    _vault_id: [u8; 32],
+    new_authority: Pubkey,
+) -> Result<()> {
+    require!(
+        new_authority != Pubkey::default(),
+        StarInvestorFeesError::InvalidAuthority
+    );
+
+    let policy = &mut ctx.accounts.policy;
+
+    // Overwrites any earlier proposal; nothing changes until it is accepted
+    policy.pending_authority = Some(new_authority);
+
+    emit!(AuthorityTransferProposed {
+        vault_id: policy.vault_id,
+        authority: policy.authority,
+        pending_authority: new_authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Authority transfer proposed to {}", new_authority);
+
+    Ok(())
+}
@@ -0,0 +1,203 @@
+use crate::constants::*;
+use crate::errors::StarInvestorFeesError;
+use crate::external_programs::{TUKTUK_CRON_PROGRAM_ID, TUKTUK_PROGRAM_ID};
+use crate::state::{CronConfig, PolicyConfig};
+use crate::tuktuk::{self, CRANK_TRANSACTION_INDEX};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(vault_id: [u8; 32])]
+pub struct RegisterCron<'info> {
+    /// Policy authority paying for the cron accounts
+    #[account(
+        mut,
+        constraint = authority.key() == policy.authority @ StarInvestorFeesError::InvalidAuthority
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [POLICY_SEED, vault_id.as_ref()],
+        bump = policy.bump
+    )]
+    pub policy: Account<'info, PolicyConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = CronConfig::LEN,
+        seeds = [CRON_SEED, vault_id.as_ref()],
+        bump
+    )]
+    pub cron_config: Account<'info, CronConfig>,
+
+    /// PDA owning the cron job
+    /// CHECK: Seeds validated, signs the cron CPIs
+    #[account(
+        seeds = [VAULT_SEED, vault_id.as_ref(), CRON_AUTHORITY_SEED],
+        bump
+    )]
+    pub cron_authority: UncheckedAccount<'info>,
+
+    /// Tuktuk cron's queue authority
+    /// CHECK: Validated by the cron program
+    pub queue_authority: UncheckedAccount<'info>,
+
+    /// Task queue authority record granting the cron program queue access
+    /// CHECK: Validated by the cron program
+    pub task_queue_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Derived from the cron authority, created by the cron program
+    #[account(
+        mut,
+        seeds = [tuktuk::USER_CRON_JOBS_SEED, cron_authority.key().as_ref()],
+        bump,
+        seeds::program = cron_program.key()
+    )]
+    pub user_cron_jobs: UncheckedAccount<'info>,
+
+    /// CHECK: Address validated and created by the cron program
+    #[account(mut)]
+    pub cron_job: UncheckedAccount<'info>,
+
+    /// CHECK: Derived from the cron authority and job name, created by the cron program
+    #[account(
+        mut,
+        seeds = [
+            tuktuk::CRON_JOB_NAME_MAPPING_SEED,
+            cron_authority.key().as_ref(),
+            CRON_JOB_NAME.as_bytes()
+        ],
+        bump,
+        seeds::program = cron_program.key()
+    )]
+    pub cron_job_name_mapping: UncheckedAccount<'info>,
+
+    /// CHECK: Derived from the cron job, created by the cron program
+    #[account(
+        mut,
+        seeds = [
+            tuktuk::CRON_JOB_TRANSACTION_SEED,
+            cron_job.key().as_ref(),
+            &CRANK_TRANSACTION_INDEX.to_le_bytes()
+        ],
+        bump,
+        seeds::program = cron_program.key()
+    )]
+    pub cron_job_transaction: UncheckedAccount<'info>,
+
+    /// Tuktuk task queue running the job
+    /// CHECK: Owner validated
+    #[account(
+        mut,
+        owner = TUKTUK_PROGRAM_ID @ StarInvestorFeesError::TuktukTaskQueueNotFound
+    )]
+    pub task_queue: UncheckedAccount<'info>,
+
+    /// First queued task of the job
+    /// CHECK: Validated by the Tuktuk program
+    #[account(mut)]
+    pub task: UncheckedAccount<'info>,
+
+    /// CHECK: Scratch account used by the cron program
+    #[account(mut)]
+    pub task_return_account_1: UncheckedAccount<'info>,
+
+    /// CHECK: Scratch account used by the cron program
+    #[account(mut)]
+    pub task_return_account_2: UncheckedAccount<'info>,
+
+    /// CHECK: Program ID validated
+    #[account(
+        constraint = cron_program.key() == TUKTUK_CRON_PROGRAM_ID
+            @ StarInvestorFeesError::InvalidTuktukProgram
+    )]
+    pub cron_program: UncheckedAccount<'info>,
+
+    /// CHECK: Program ID validated
+    #[account(
+        constraint = tuktuk_program.key() == TUKTUK_PROGRAM_ID
+            @ StarInvestorFeesError::InvalidTuktukProgram
+    )]
+    pub tuktuk_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<RegisterCron>,
+    vault_id: [u8; 32],
+    crank_url: String,
+    crank_signer: Pubkey,
+) -> Result<()> {
+    tuktuk::validate_crank_config(&crank_url, &crank_signer)?;
+
+    let authority_bump = ctx.bumps.cron_authority;
+    let seeds = &[
+        VAULT_SEED,
+        vault_id.as_ref(),
+        CRON_AUTHORITY_SEED,
+        &[authority_bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let accounts = &ctx.accounts;
+    let mut cron_authority_signer = accounts.cron_authority.to_account_info();
+    cron_authority_signer.is_signer = true;
+
+    // Create the job on the daily schedule
+    tuktuk::invoke_cron(
+        &accounts.cron_program,
+        &[
+            accounts.authority.to_account_info(),
+            accounts.queue_authority.to_account_info(),
+            accounts.task_queue_authority.to_account_info(),
+            cron_authority_signer.clone(),
+            accounts.user_cron_jobs.to_account_info(),
+            accounts.cron_job.to_account_info(),
+            accounts.cron_job_name_mapping.to_account_info(),
+            accounts.task_queue.to_account_info(),
+            accounts.task.to_account_info(),
+            accounts.task_return_account_1.to_account_info(),
+            accounts.task_return_account_2.to_account_info(),
+            accounts.tuktuk_program.to_account_info(),
+            accounts.system_program.to_account_info(),
+        ],
+        tuktuk::initialize_cron_job_data(DAILY_CRON_SCHEDULE, CRON_JOB_NAME)?,
+        signer_seeds,
+    )?;
+
+    // Each run fetches the day's pages from the crank service
+    tuktuk::invoke_cron(
+        &accounts.cron_program,
+        &[
+            accounts.authority.to_account_info(),
+            cron_authority_signer,
+            accounts.cron_job.to_account_info(),
+            accounts.cron_job_transaction.to_account_info(),
+            accounts.system_program.to_account_info(),
+        ],
+        tuktuk::add_remote_transaction_data(CRANK_TRANSACTION_INDEX, &crank_url, &crank_signer)?,
+        signer_seeds,
+    )?;
+
+    let cron_config = &mut ctx.accounts.cron_config;
+    cron_config.vault_id = vault_id;
+    cron_config.cron_job = ctx.accounts.cron_job.key();
+    cron_config.task_queue = ctx.accounts.task_queue.key();
+    cron_config.crank_signer = crank_signer;
+    cron_config.authority_bump = authority_bump;
+    cron_config.bump = ctx.bumps.cron_config;
+
+    msg!("Cron job registered: {}", cron_config.cron_job);
+
+    emit!(CronRegistered {
+        vault_id,
+        cron_job: cron_config.cron_job,
+        task_queue: cron_config.task_queue,
+        crank_url,
+        crank_signer,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use crate::constants::*;
+use crate::errors::StarInvestorFeesError;
+use crate::state::{DistributionProgress, InvestorMarker, InvestorRegistryPage, PolicyConfig};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(vault_id: [u8; 32], page_index: u16, stream: Pubkey)]
+pub struct RemoveInvestor<'info> {
+    #[account(
+        mut,
+        constraint = authority.key() == policy.authority @ StarInvestorFeesError::InvalidAuthority
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [POLICY_SEED, vault_id.as_ref()],
+        bump = policy.bump
+    )]
+    pub policy: Account<'info, PolicyConfig>,
+
+    /// Distribution progress (page layout cannot change mid-day)
+    #[account(
+        seeds = [PROGRESS_SEED, vault_id.as_ref()],
+        bump = progress.bump,
+        constraint = !progress.is_day_in_progress() @ StarInvestorFeesError::DistributionInProgress
+    )]
+    pub progress: Account<'info, DistributionProgress>,
+
+    #[account(
+        mut,
+        seeds = [REGISTRY_SEED, vault_id.as_ref(), &page_index.to_le_bytes()],
+        bump = registry_page.bump
+    )]
+    pub registry_page: Account<'info, InvestorRegistryPage>,
+
+    /// Stream's registration marker, closed so the stream can be registered again
+    #[account(
+        mut,
+        seeds = [INVESTOR_MARKER_SEED, vault_id.as_ref(), stream.as_ref()],
+        bump = investor_marker.bump,
+        constraint = investor_marker.page_index == page_index @ StarInvestorFeesError::InvestorNotRegistered,
+        close = authority
+    )]
+    pub investor_marker: Account<'info, InvestorMarker>,
+}
+
+pub fn handler(
+    ctx: Context<RemoveInvestor>,
+    vault_id: [u8; 32],
+    page_index: u16,
+    stream: Pubkey,
+) -> Result<()> {
+    let registry_page = &mut ctx.accounts.registry_page;
+
+    let index = registry_page
+        .find(&stream)
+        .ok_or(StarInvestorFeesError::InvestorNotRegistered)?;
+    registry_page.entries.remove(index);
+
+    emit!(InvestorRemoved {
+        vault_id,
+        page_index,
+        stream,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Investor {} removed from page {}", stream, page_index);
+
+    Ok(())
+}
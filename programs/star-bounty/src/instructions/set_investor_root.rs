@@ -0,0 +1,77 @@
+use crate::constants::*;
+use crate::errors::StarInvestorFeesError;
+use crate::state::{DistributionProgress, PolicyConfig};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(vault_id: [u8; 32])]
+pub struct SetInvestorRoot<'info> {
+    #[account(
+        constraint = authority.key() == policy.authority @ StarInvestorFeesError::InvalidAuthority
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [POLICY_SEED, vault_id.as_ref()],
+        bump = policy.bump
+    )]
+    pub policy: Account<'info, PolicyConfig>,
+
+    /// Distribution progress (the investor set cannot change mid-day)
+    #[account(
+        seeds = [PROGRESS_SEED, vault_id.as_ref()],
+        bump = progress.bump,
+        constraint = !progress.is_day_in_progress() @ StarInvestorFeesError::DistributionInProgress
+    )]
+    pub progress: Account<'info, DistributionProgress>,
+}
+
+pub fn handler(
+    ctx: Context<SetInvestorRoot>,
+    _vault_id: [u8; 32],
+    investor_root: [u8; 32],
+    investor_count: u32,
+    page_size: u16,
+) -> Result<()> {
+    let policy = &mut ctx.accounts.policy;
+
+    if investor_root == [0u8; 32] {
+        // A zero root switches back to the on-chain registry
+        require!(
+            investor_count == 0 && page_size == 0,
+            StarInvestorFeesError::PolicyConfigInvalid
+        );
+    } else {
+        require!(
+            investor_count > 0 && page_size > 0 && page_size as usize <= MAX_INVESTORS_PER_PAGE,
+            StarInvestorFeesError::PolicyConfigInvalid
+        );
+        require!(
+            investor_count.div_ceil(page_size as u32) <= MAX_PAGES_PER_DAY as u32,
+            StarInvestorFeesError::InvalidPaginationCursor
+        );
+    }
+
+    let previous_root = policy.investor_root;
+    policy.investor_root = investor_root;
+    policy.investor_count = investor_count;
+    policy.merkle_page_size = page_size;
+
+    emit!(InvestorRootUpdated {
+        vault_id: policy.vault_id,
+        previous_root,
+        new_root: investor_root,
+        investor_count,
+        page_size,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Investor root updated: {} investors, {} per page",
+        investor_count,
+        page_size
+    );
+
+    Ok(())
+}
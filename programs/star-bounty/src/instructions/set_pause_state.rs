@@ -0,0 +1,36 @@
+use crate::constants::*;
+use crate::errors::StarInvestorFeesError;
+use crate::state::PolicyConfig;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(vault_id: [u8; 32])]
+pub struct SetPauseState<'info> {
+    #[account(
+        constraint = authority.key() == policy.authority @ StarInvestorFeesError::InvalidAuthority
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [POLICY_SEED, vault_id.as_ref()],
+        bump = policy.bump
+    )]
+    pub policy: Account<'info, PolicyConfig>,
+}
+
+pub fn handler(ctx: Context<SetPauseState>, _vault_id: [u8; 32], paused: bool) -> Result<()> {
+    let policy = &mut ctx.accounts.policy;
+
+    policy.paused = paused;
+
+    emit!(PauseStateChanged {
+        vault_id: policy.vault_id,
+        paused,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Pause state set to {}", paused);
+
+    Ok(())
+}
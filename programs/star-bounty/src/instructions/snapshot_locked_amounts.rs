@@ -0,0 +1,202 @@
+use crate::constants::*;
+use crate::errors::StarInvestorFeesError;
+use crate::state::{DistributionProgress, InvestorProof, InvestorRegistryPage, PolicyConfig};
+use crate::utils::{investors, receipts, safe_math, time, validation};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(vault_id: [u8; 32], page_number: u16)]
+pub struct SnapshotLockedAmounts<'info> {
+    /// Cranker who executes the snapshot (can be anyone - permissionless)
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    /// Policy configuration account
+    #[account(
+        seeds = [POLICY_SEED, vault_id.as_ref()],
+        bump = policy.bump,
+        constraint = !policy.paused @ StarInvestorFeesError::ProgramPaused
+    )]
+    pub policy: Account<'info, PolicyConfig>,
+
+    /// Distribution progress tracking account
+    #[account(
+        mut,
+        seeds = [PROGRESS_SEED, vault_id.as_ref()],
+        bump = progress.bump
+    )]
+    pub progress: Account<'info, DistributionProgress>,
+
+    /// Registry page listing the investors on this page (omitted when the policy uses a Merkle root)
+    #[account(
+        seeds = [REGISTRY_SEED, vault_id.as_ref(), &page_number.to_le_bytes()],
+        bump = registry_page.bump
+    )]
+    pub registry_page: Option<Box<Account<'info, InvestorRegistryPage>>>,
+
+    /// Lock program selected by the policy (Streamflow, Jupiter Lock, ...)
+    /// CHECK: Program ID validated
+    #[account(
+        constraint = lock_program.key() == policy.lock_source.program_id()
+            @ StarInvestorFeesError::InvalidLockProgram
+    )]
+    pub lock_program: UncheckedAccount<'info>,
+
+    /// System program (creates investor receipts)
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SnapshotLockedAmounts<'info>>,
+    vault_id: [u8; 32],
+    page_number: u16,
+    proofs: Vec<InvestorProof>,
+) -> Result<()> {
+    let current_ts = Clock::get()?.unix_timestamp;
+    let policy = &ctx.accounts.policy;
+    let progress = &mut ctx.accounts.progress;
+
+    msg!("=== Locked Snapshot Page {} ===", page_number);
+
+    // Validate vault ID matches
+    validation::validate_vault_id(&progress.vault_id, &vault_id)?;
+
+    if page_number == 0 {
+        // The first snapshot page opens a new distribution day
+        require!(
+            progress.is_new_day_ready(current_ts),
+            StarInvestorFeesError::DistributionTooEarly
+        );
+        require!(
+            !progress.is_day_in_progress(),
+            StarInvestorFeesError::DistributionInProgress
+        );
+        // The investor source fixes the page layout for the whole day
+        let day_pages = policy.day_pages();
+        require!(
+            day_pages > 0,
+            StarInvestorFeesError::InvalidPaginationCursor
+        );
+
+        progress.reset_for_new_day(time::get_day_start(current_ts), day_pages);
+        progress.last_distribution_ts = current_ts;
+
+        msg!("Starting new distribution day with {} pages", day_pages);
+    } else {
+        require!(
+            !progress.day_finalized,
+            StarInvestorFeesError::DayAlreadyFinalized
+        );
+    }
+
+    // Snapshot pages are taken in order, and only until every page is covered
+    validation::validate_page_number(page_number, progress.snapshot_pages_done)?;
+    require!(
+        page_number < progress.total_pages,
+        StarInvestorFeesError::InvalidPageNumber
+    );
+
+    // Remaining accounts: (stream, receipt) for each investor on this page
+    let remaining_accounts = &ctx.remaining_accounts;
+    let streams = investors::page_streams(remaining_accounts, SNAPSHOT_ACCOUNTS_PER_INVESTOR)?;
+
+    // Every investor on the page must be registered or proven against the root
+    let page_investors = investors::resolve_page(
+        policy,
+        ctx.accounts.registry_page.as_deref().map(|page| &**page),
+        page_number,
+        &streams,
+        &proofs,
+    )?;
+
+    // All pages are evaluated at the day's start so payouts see the same amounts
+    let day_start = progress.current_day_start;
+    let snapshot_ts = day_start as u64;
+    let mut locked_this_page = 0u64;
+
+    for (investor, page_investor) in remaining_accounts
+        .chunks(SNAPSHOT_ACCOUNTS_PER_INVESTOR)
+        .zip(page_investors.iter())
+    {
+        let (stream_account_info, receipt_info) = (&investor[0], &investor[1]);
+
+        // Each stream may only be counted once per day
+        let mut receipt = receipts::load_or_create(
+            receipt_info,
+            stream_account_info.key,
+            &vault_id,
+            &ctx.accounts.cranker.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            ctx.program_id,
+        )?;
+        require!(
+            receipt.last_snapshot_day != day_start,
+            StarInvestorFeesError::DuplicateInvestor
+        );
+
+        let lock = policy
+            .lock_source
+            .load(stream_account_info, &policy.vault_id)?;
+        require_keys_eq!(
+            lock.mint(),
+            policy.base_mint,
+            StarInvestorFeesError::StreamMintMismatch
+        );
+        // A stale registry entry or proof fails here, before the day's fees are claimed
+        require_keys_eq!(
+            lock.recipient(),
+            page_investor.recipient,
+            StarInvestorFeesError::InvalidInvestorData
+        );
+        let locked_amount = lock
+            .locked_amount(snapshot_ts)
+            .min(page_investor.weight_cap);
+
+        // Payouts weigh the investor by exactly what was counted here
+        receipt.last_snapshot_day = day_start;
+        receipt.snapshot_locked_amount = locked_amount;
+        receipt.recipient = lock.recipient();
+        receipts::save(receipt_info, &receipt)?;
+
+        locked_this_page = safe_math::add(locked_this_page, locked_amount)?;
+    }
+
+    progress.total_locked_snapshot =
+        safe_math::add(progress.total_locked_snapshot, locked_this_page)?;
+    progress.snapshot_pages_done = safe_math::add(progress.snapshot_pages_done as u64, 1)? as u16;
+
+    msg!("Locked on page: {}", locked_this_page);
+    msg!("Locked so far: {}", progress.total_locked_snapshot);
+
+    if progress.is_snapshot_complete() {
+        // f_locked is clamped to 100%, so an oversized total is flagged rather
+        // than stalling the day (Y0 cannot be changed)
+        if progress.total_locked_snapshot > policy.y0_total_allocation {
+            msg!(
+                "Locked total {} exceeds Y0 {}",
+                progress.total_locked_snapshot,
+                policy.y0_total_allocation
+            );
+            emit!(LockedTotalExceedsAllocation {
+                vault_id,
+                total_locked: progress.total_locked_snapshot,
+                y0_total_allocation: policy.y0_total_allocation,
+                day_start: progress.current_day_start,
+                timestamp: current_ts,
+            });
+        }
+
+        msg!("Snapshot complete - payouts may start");
+    }
+
+    emit!(LockedSnapshotPage {
+        vault_id,
+        page_number,
+        total_pages: progress.total_pages,
+        locked_this_page,
+        total_locked: progress.total_locked_snapshot,
+        timestamp: current_ts,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use crate::constants::*;
+use crate::errors::StarInvestorFeesError;
+use crate::state::{DistributionProgress, PolicyConfig};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(vault_id: [u8; 32])]
+pub struct UpdateCreator<'info> {
+    #[account(
+        constraint = authority.key() == policy.authority @ StarInvestorFeesError::InvalidAuthority
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [POLICY_SEED, vault_id.as_ref()],
+        bump = policy.bump
+    )]
+    pub policy: Account<'info, PolicyConfig>,
+
+    /// Distribution progress (creator cannot change mid-day)
+    #[account(
+        seeds = [PROGRESS_SEED, vault_id.as_ref()],
+        bump = progress.bump,
+        constraint = !progress.is_day_in_progress() @ StarInvestorFeesError::DistributionInProgress
+    )]
+    pub progress: Account<'info, DistributionProgress>,
+
+    /// New creator wallet (will receive remainder fees)
+    /// CHECK: Address stored in policy
+    pub new_creator: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<UpdateCreator>, _vault_id: [u8; 32]) -> Result<()> {
+    let new_creator = ctx.accounts.new_creator.key();
+
+    require!(
+        new_creator != Pubkey::default(),
+        StarInvestorFeesError::InvalidCreator
+    );
+
+    let policy = &mut ctx.accounts.policy;
+
+    let previous_creator = policy.creator;
+    policy.creator = new_creator;
+
+    emit!(CreatorUpdated {
+        vault_id: policy.vault_id,
+        previous_creator,
+        new_creator,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Creator updated from {} to {}",
+        previous_creator,
+        new_creator
+    );
+
+    Ok(())
+}
@@ -1,6 +1,6 @@
 use crate::constants::*;
 use crate::errors::StarInvestorFeesError;
-use crate::state::PolicyConfig;
+use crate::state::{DistributionProgress, PolicyConfig};
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
@@ -17,6 +17,14 @@ pub struct UpdatePolicy<'info> {
         bump = policy.bump
     )]
     pub policy: Account<'info, PolicyConfig>,
+
+    /// Distribution progress (policy cannot change mid-day)
+    #[account(
+        seeds = [PROGRESS_SEED, vault_id.as_ref()],
+        bump = progress.bump,
+        constraint = !progress.is_day_in_progress() @ StarInvestorFeesError::DistributionInProgress
+    )]
+    pub progress: Account<'info, DistributionProgress>,
 }
 
 pub fn handler(
@@ -25,6 +33,7 @@ pub fn handler(
     investor_fee_share_bps: Option<u16>,
     daily_cap_lamports: Option<Option<u64>>,
     min_payout_lamports: Option<u64>,
+    finalize_grace_seconds: Option<i64>,
 ) -> Result<()> {
     let policy = &mut ctx.accounts.policy;
 
@@ -47,11 +56,21 @@ pub fn handler(
         policy.min_payout_lamports = min;
     }
 
+    // Update finalize grace window if provided
+    if let Some(grace) = finalize_grace_seconds {
+        require!(
+            (MIN_FINALIZE_GRACE_SECONDS..=SECONDS_PER_DAY).contains(&grace),
+            StarInvestorFeesError::InvalidFinalizeGrace
+        );
+        policy.finalize_grace_seconds = grace;
+    }
+
     emit!(PolicyUpdated {
         vault_id: policy.vault_id,
         investor_fee_share_bps: policy.investor_fee_share_bps,
         daily_cap_lamports: policy.daily_cap_lamports,
         min_payout_lamports: policy.min_payout_lamports,
+        finalize_grace_seconds: policy.finalize_grace_seconds,
         timestamp: Clock::get()?.unix_timestamp,
     });
 
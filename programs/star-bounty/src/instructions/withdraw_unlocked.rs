@@ -0,0 +1,89 @@
+use crate::constants::*;
+use crate::errors::StarInvestorFeesError;
+use crate::state::InvestorLock;
+use crate::utils::safe_math;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+#[derive(Accounts)]
+#[instruction(vault_id: [u8; 32])]
+pub struct WithdrawUnlocked<'info> {
+    /// Investor the lock belongs to
+    pub recipient: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [INVESTOR_LOCK_SEED, vault_id.as_ref(), recipient.key().as_ref()],
+        bump = investor_lock.bump,
+        has_one = recipient @ StarInvestorFeesError::UnauthorizedSigner,
+        has_one = mint @ StarInvestorFeesError::InvalidLockAccount
+    )]
+    pub investor_lock: Account<'info, InvestorLock>,
+
+    /// Escrow token account holding the locked base tokens
+    #[account(
+        mut,
+        seeds = [LOCK_ESCROW_SEED, investor_lock.key().as_ref()],
+        bump = investor_lock.escrow_bump
+    )]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Token account receiving the unlocked tokens
+    #[account(
+        mut,
+        token::mint = mint,
+        token::token_program = token_program
+    )]
+    pub recipient_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler(ctx: Context<WithdrawUnlocked>, vault_id: [u8; 32]) -> Result<()> {
+    let current_ts = Clock::get()?.unix_timestamp;
+    let investor_lock = &mut ctx.accounts.investor_lock;
+
+    let amount = investor_lock.available_to_withdraw(current_ts);
+    require!(amount > 0, StarInvestorFeesError::NothingToWithdraw);
+
+    investor_lock.withdrawn_amount = safe_math::add(investor_lock.withdrawn_amount, amount)?;
+
+    let recipient = investor_lock.recipient;
+    let seeds = &[
+        INVESTOR_LOCK_SEED,
+        vault_id.as_ref(),
+        recipient.as_ref(),
+        &[investor_lock.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.escrow.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.recipient_token_account.to_account_info(),
+                authority: investor_lock.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    emit!(InvestorLockWithdrawn {
+        vault_id,
+        lock: investor_lock.key(),
+        recipient,
+        amount,
+        total_withdrawn: investor_lock.withdrawn_amount,
+        timestamp: current_ts,
+    });
+
+    msg!("Withdrew {} unlocked tokens", amount);
+
+    Ok(())
+}
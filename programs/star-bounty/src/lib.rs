@@ -2,152 +2,471 @@ use anchor_lang::prelude::*;
 
 // pub mod constants;
 pub mod constants;
+pub mod damm_v2;
 pub mod errors;
+pub mod external_programs;
 pub mod instructions;
+pub mod lock_source;
 pub mod state;
+pub mod tuktuk;
 pub mod utils;
 
 use instructions::*;
+use state::{InvestorLockStatus, InvestorProof, LockSourceKind};
+
+pub use entry::*;
 
 declare_id!("2WYpJx4kYNRKpCm4wPPNZjWFJvpqU2KCCxa88xcHuKdL");
 
-#[program]
-pub mod star_bounty {
+// `#[program]` emits its IDL instructions next to the program module, and they call the
+// deprecated `AccountInfo::realloc`; this module exists only to scope the allow to them
+#[allow(deprecated)]
+mod entry {
     use super::*;
 
-    /// Initialize the honorary DAMM v2 position for quote-only fee accrual
-    ///
-    /// This creates a special liquidity position owned by the program that only
-    /// accrues fees in the quote token (not the base token).
-    ///
-    /// # Arguments
-    /// * `vault_id` - Unique identifier for this vault (32 bytes)
-    /// * `lower_tick_index` - Lower bound of the price range
-    /// * `upper_tick_index` - Upper bound of the price range
-    pub fn initialize_honorary_position(
-        ctx: Context<InitializeHonoraryPosition>,
-        vault_id: [u8; 32],
-    ) -> Result<()> {
-        // instructions::initialize_honorary_position::handler(ctx, vault_id, lower_tick_index, upper_tick_index)
-        instructions::initialize_honorary_position::handler(ctx, vault_id)
-    }
+    #[program]
+    pub mod star_bounty {
+        use super::*;
 
-    /// Initialize the policy configuration
-    ///
-    /// Sets up the fee distribution parameters and configuration.
-    ///
-    /// # Arguments
-    /// * `vault_id` - Unique identifier for this vault
-    /// * `investor_fee_share_bps` - Investor fee share in basis points (0-10000)
-    /// * `daily_cap_lamports` - Optional daily distribution cap
-    /// * `min_payout_lamports` - Minimum payout threshold
-    /// * `y0_total_allocation` - Total investor allocation at TGE
-    pub fn initialize_policy(
-        ctx: Context<InitializePolicy>,
-        vault_id: [u8; 32],
-        investor_fee_share_bps: u16,
-        daily_cap_lamports: Option<u64>,
-        min_payout_lamports: u64,
-        y0_total_allocation: u64,
-    ) -> Result<()> {
-        instructions::initialize_policy::handler(
-            ctx,
-            vault_id,
-            investor_fee_share_bps,
-            daily_cap_lamports,
-            min_payout_lamports,
-            y0_total_allocation,
-        )
-    }
+        /// Initialize the honorary DAMM v2 position for quote-only fee accrual
+        ///
+        /// This creates a special liquidity position owned by the program that only
+        /// accrues fees in the quote token (not the base token).
+        ///
+        /// # Arguments
+        /// * `vault_id` - Unique identifier for this vault (32 bytes)
+        /// * `lower_tick_index` - Lower bound of the price range
+        /// * `upper_tick_index` - Upper bound of the price range
+        pub fn initialize_honorary_position(
+            ctx: Context<InitializeHonoraryPosition>,
+            vault_id: [u8; 32],
+        ) -> Result<()> {
+            // instructions::initialize_honorary_position::handler(ctx, vault_id, lower_tick_index, upper_tick_index)
+            instructions::initialize_honorary_position::handler(ctx, vault_id)
+        }
+
+        /// Initialize the policy configuration
+        ///
+        /// Sets up the fee distribution parameters and configuration.
+        ///
+        /// # Arguments
+        /// * `vault_id` - Unique identifier for this vault
+        /// * `investor_fee_share_bps` - Investor fee share in basis points (0-10000)
+        /// * `daily_cap_lamports` - Optional daily distribution cap
+        /// * `min_payout_lamports` - Minimum payout threshold
+        /// * `y0_total_allocation` - Total investor allocation at TGE
+        /// * `lock_source` - Lock program investors' locked amounts are read from
+        pub fn initialize_policy(
+            ctx: Context<InitializePolicy>,
+            vault_id: [u8; 32],
+            investor_fee_share_bps: u16,
+            daily_cap_lamports: Option<u64>,
+            min_payout_lamports: u64,
+            y0_total_allocation: u64,
+            lock_source: LockSourceKind,
+        ) -> Result<()> {
+            instructions::initialize_policy::handler(
+                ctx,
+                vault_id,
+                investor_fee_share_bps,
+                daily_cap_lamports,
+                min_payout_lamports,
+                y0_total_allocation,
+                lock_source,
+            )
+        }
+
+        /// Create the vault's quote and base treasury token accounts
+        ///
+        /// Both are PDAs owned by the position owner PDA, created under each
+        /// mint's token program. Must run before the first distribution.
+        ///
+        /// # Arguments
+        /// * `vault_id` - Vault identifier
+        pub fn initialize_treasury(
+            ctx: Context<InitializeTreasury>,
+            vault_id: [u8; 32],
+        ) -> Result<()> {
+            instructions::initialize_treasury::handler(ctx, vault_id)
+        }
+
+        pub fn add_liquidity_quote_only(
+            ctx: Context<AddLiquidityQuoteOnly>,
+            vault_id: [u8; 32],
+            liquidity_delta: u128,
+            token_a_amount_threshold: u64,
+            token_b_amount_threshold: u64,
+        ) -> Result<()> {
+            instructions::add_liquidity_quote_only::handler(
+                ctx,
+                vault_id,
+                liquidity_delta,
+                token_a_amount_threshold,
+                token_b_amount_threshold,
+            )
+        }
+        /// Append an empty investor registry page (admin only)
+        ///
+        /// Registry pages fix the daily page layout: page N of the registry is
+        /// processed as crank page N.
+        ///
+        /// # Arguments
+        /// * `vault_id` - Unique identifier for this vault
+        pub fn create_registry_page(
+            ctx: Context<CreateRegistryPage>,
+            vault_id: [u8; 32],
+        ) -> Result<()> {
+            instructions::create_registry_page::handler(ctx, vault_id)
+        }
+
+        /// Register an investor on a registry page (admin only)
+        ///
+        /// A per-stream marker PDA keeps each stream on a single page.
+        ///
+        /// # Arguments
+        /// * `vault_id` - Unique identifier for this vault
+        /// * `page_index` - Registry page to add the investor to
+        /// * `stream` - Investor's lock account (e.g. a Streamflow stream)
+        /// * `recipient` - Wallet that owns the investor's quote token account
+        pub fn add_investor(
+            ctx: Context<AddInvestor>,
+            vault_id: [u8; 32],
+            page_index: u16,
+            stream: Pubkey,
+            recipient: Pubkey,
+        ) -> Result<()> {
+            instructions::add_investor::handler(ctx, vault_id, page_index, stream, recipient)
+        }
+
+        /// Remove an investor from a registry page (admin only)
+        ///
+        /// Closes the stream's marker PDA, refunding its rent to the authority.
+        ///
+        /// # Arguments
+        /// * `vault_id` - Unique identifier for this vault
+        /// * `page_index` - Registry page holding the investor
+        /// * `stream` - Investor's lock account (e.g. a Streamflow stream)
+        pub fn remove_investor(
+            ctx: Context<RemoveInvestor>,
+            vault_id: [u8; 32],
+            page_index: u16,
+            stream: Pubkey,
+        ) -> Result<()> {
+            instructions::remove_investor::handler(ctx, vault_id, page_index, stream)
+        }
+
+        /// Create and fund a native investor lock (admin only)
+        ///
+        /// Locks base tokens in a program-owned escrow. Nothing unlocks before the
+        /// cliff, `cliff_amount` unlocks at the cliff and the rest unlocks linearly
+        /// until `end_time`. Usable as a lock source via `LockSourceKind::NativeEscrow`.
+        ///
+        /// # Arguments
+        /// * `vault_id` - Unique identifier for this vault
+        /// * `recipient` - Investor wallet entitled to the tokens
+        /// * `amount` - Base tokens to deposit
+        /// * `start_time` - Schedule start timestamp
+        /// * `cliff_time` - Cliff timestamp
+        /// * `end_time` - Timestamp at which everything is unlocked
+        /// * `cliff_amount` - Tokens unlocked at the cliff
+        #[allow(clippy::too_many_arguments)]
+        pub fn create_investor_lock(
+            ctx: Context<CreateInvestorLock>,
+            vault_id: [u8; 32],
+            recipient: Pubkey,
+            amount: u64,
+            start_time: i64,
+            cliff_time: i64,
+            end_time: i64,
+            cliff_amount: u64,
+        ) -> Result<()> {
+            instructions::create_investor_lock::handler(
+                ctx,
+                vault_id,
+                recipient,
+                amount,
+                start_time,
+                cliff_time,
+                end_time,
+                cliff_amount,
+            )
+        }
+
+        /// Withdraw unlocked tokens from a native investor lock
+        ///
+        /// # Arguments
+        /// * `vault_id` - Unique identifier for this vault
+        pub fn withdraw_unlocked(ctx: Context<WithdrawUnlocked>, vault_id: [u8; 32]) -> Result<()> {
+            instructions::withdraw_unlocked::handler(ctx, vault_id)
+        }
+
+        /// Query a native investor lock's balances at the current time
+        ///
+        /// # Arguments
+        /// * `vault_id` - Unique identifier for this vault
+        /// * `recipient` - Investor wallet the lock belongs to
+        pub fn get_investor_lock_status(
+            ctx: Context<GetInvestorLockStatus>,
+            vault_id: [u8; 32],
+            recipient: Pubkey,
+        ) -> Result<InvestorLockStatus> {
+            instructions::get_investor_lock_status::handler(ctx, vault_id, recipient)
+        }
+
+        /// Set, rotate or clear the investor Merkle root (admin only)
+        ///
+        /// A non-zero root replaces the registry: investors are then proven with
+        /// (stream, investor_owner, weight_cap) leaves passed in the crank's
+        /// instruction data. A zero root switches back to the registry.
+        ///
+        /// # Arguments
+        /// * `vault_id` - Unique identifier for this vault
+        /// * `investor_root` - Merkle root, or all zeros to use the registry
+        /// * `investor_count` - Number of leaves under the root
+        /// * `page_size` - Investors per crank page (up to 20)
+        pub fn set_investor_root(
+            ctx: Context<SetInvestorRoot>,
+            vault_id: [u8; 32],
+            investor_root: [u8; 32],
+            investor_count: u32,
+            page_size: u16,
+        ) -> Result<()> {
+            instructions::set_investor_root::handler(
+                ctx,
+                vault_id,
+                investor_root,
+                investor_count,
+                page_size,
+            )
+        }
+
+        /// Snapshot locked amounts - first phase of the daily distribution
+        ///
+        /// Page 0 opens a new day (once per 24 hours). Every page adds its
+        /// investors' locked amounts to the day's global total, which payout
+        /// pages use as the pro-rata denominator.
+        ///
+        /// # Arguments
+        /// * `vault_id` - Unique identifier for this vault
+        /// * `page_number` - Current page being snapshotted (0-indexed)
+        /// * `proofs` - Merkle proofs for each investor on the page (empty in registry mode)
+        ///
+        /// # Remaining Accounts
+        /// For each investor on the page, in registry or proof order:
+        /// - Investor lock account from the policy's lock source (read-only)
+        /// - Investor receipt PDA (writable, created on first use)
+        pub fn snapshot_locked_amounts<'info>(
+            ctx: Context<'_, '_, 'info, 'info, SnapshotLockedAmounts<'info>>,
+            vault_id: [u8; 32],
+            page_number: u16,
+            proofs: Vec<InvestorProof>,
+        ) -> Result<()> {
+            instructions::snapshot_locked_amounts::handler(ctx, vault_id, page_number, proofs)
+        }
+
+        /// Main distribution crank - claims fees and distributes to investors
+        ///
+        /// Runs after the locked snapshot covers every page. Page 0 claims the
+        /// day's fees and fixes the investor pool; each page then pays its
+        /// investors pro-rata against the global locked total.
+        ///
+        /// # Arguments
+        /// * `vault_id` - Unique identifier for this vault
+        /// * `page_number` - Current page being processed (0-indexed)
+        /// * `proofs` - Merkle proofs for each investor on the page (empty in registry mode)
+        ///
+        /// # Remaining Accounts
+        /// For each investor on the page, in registry or proof order:
+        /// - Investor lock account (read-only; only its key is used, weights come from the receipt)
+        /// - Investor quote token account (writable)
+        /// - Investor receipt PDA (writable)
+        /// - Investor accrual PDA (writable, created on first use)
+        pub fn distribute_fees<'info>(
+            ctx: Context<'_, '_, 'info, 'info, DistributeFees<'info>>,
+            vault_id: [u8; 32],
+            page_number: u16,
+            proofs: Vec<InvestorProof>,
+        ) -> Result<()> {
+            instructions::distribute_fees::handler(ctx, vault_id, page_number, proofs)
+        }
+
+        /// Pay an investor's accrued balance in full and close the accrual
+        ///
+        /// Signed by the investor named in the stream's receipt. Covers balances
+        /// that never reach the payout minimum; the rent goes back to the cranker
+        /// that created the accrual.
+        ///
+        /// # Arguments
+        /// * `vault_id` - Unique identifier for this vault
+        pub fn claim_accrual(ctx: Context<ClaimAccrual>, vault_id: [u8; 32]) -> Result<()> {
+            instructions::claim_accrual::handler(ctx, vault_id)
+        }
+
+        /// Close an investor receipt between days, refunding its rent payer
+        ///
+        /// Refused while the stream's accrual still holds a balance.
+        ///
+        /// # Arguments
+        /// * `vault_id` - Unique identifier for this vault
+        pub fn close_investor_receipt(
+            ctx: Context<CloseInvestorReceipt>,
+            vault_id: [u8; 32],
+        ) -> Result<()> {
+            instructions::close_investor_receipt::handler(ctx, vault_id)
+        }
+
+        /// Close an empty investor accrual, refunding its rent payer
+        ///
+        /// # Arguments
+        /// * `vault_id` - Unique identifier for this vault
+        pub fn close_investor_accrual(
+            ctx: Context<CloseInvestorAccrual>,
+            vault_id: [u8; 32],
+        ) -> Result<()> {
+            instructions::close_investor_accrual::handler(ctx, vault_id)
+        }
+
+        /// Finalize the day by sending remainder to creator
+        ///
+        /// Should be called after all pages have been processed.
+        /// Sends any remaining fees to the project creator. Once the policy's
+        /// grace window has passed it may run with pages pending; their
+        /// investors' share carries into the next day.
+        ///
+        /// # Arguments
+        /// * `vault_id` - Unique identifier for this vault
+        pub fn finalize_day_distribution(
+            ctx: Context<FinalizeDayDistribution>,
+            vault_id: [u8; 32],
+        ) -> Result<()> {
+            instructions::finalize_day_distribution::handler(ctx, vault_id)
+        }
+
+        /// Register a Tuktuk cron job opening the vault's day every 24h (admin only)
+        ///
+        /// Each run executes one transaction served by the crank service and
+        /// signed by `crank_signer`, normally snapshot page 0. A day needs
+        /// `2 * pages + 1` transactions and no instruction queues follow-up
+        /// tasks, so the remaining snapshot, payout and finalize transactions must
+        /// still be cranked off-chain; finalize's grace window bounds a stall.
+        ///
+        /// # Arguments
+        /// * `vault_id` - Unique identifier for this vault
+        /// * `crank_url` - URL the cron job fetches the day's crank transactions from
+        /// * `crank_signer` - Key the crank service signs its transactions with
+        pub fn register_cron(
+            ctx: Context<RegisterCron>,
+            vault_id: [u8; 32],
+            crank_url: String,
+            crank_signer: Pubkey,
+        ) -> Result<()> {
+            instructions::register_cron::handler(ctx, vault_id, crank_url, crank_signer)
+        }
+
+        /// Top up the vault's cron job with SOL for crank fees
+        ///
+        /// # Arguments
+        /// * `vault_id` - Unique identifier for this vault
+        /// * `lamports` - Amount to transfer to the cron job
+        pub fn fund_cron(ctx: Context<FundCron>, vault_id: [u8; 32], lamports: u64) -> Result<()> {
+            instructions::fund_cron::handler(ctx, vault_id, lamports)
+        }
+
+        /// Close the vault's cron job, refunding its balance (admin only)
+        ///
+        /// # Arguments
+        /// * `vault_id` - Unique identifier for this vault
+        pub fn cancel_cron(ctx: Context<CancelCron>, vault_id: [u8; 32]) -> Result<()> {
+            instructions::cancel_cron::handler(ctx, vault_id)
+        }
+
+        /// Update policy configuration (admin only)
+        ///
+        /// Allows the authority to update distribution parameters between days.
+        ///
+        /// # Arguments
+        /// * `vault_id` - Unique identifier for this vault
+        /// * `investor_fee_share_bps` - New investor fee share (optional)
+        /// * `daily_cap_lamports` - New daily cap (optional)
+        /// * `min_payout_lamports` - New minimum payout (optional)
+        /// * `finalize_grace_seconds` - New grace window before pending pages can be finalized (optional)
+        pub fn update_policy(
+            ctx: Context<UpdatePolicy>,
+            vault_id: [u8; 32],
+            investor_fee_share_bps: Option<u16>,
+            daily_cap_lamports: Option<Option<u64>>,
+            min_payout_lamports: Option<u64>,
+            finalize_grace_seconds: Option<i64>,
+        ) -> Result<()> {
+            instructions::update_policy::handler(
+                ctx,
+                vault_id,
+                investor_fee_share_bps,
+                daily_cap_lamports,
+                min_payout_lamports,
+                finalize_grace_seconds,
+            )
+        }
+
+        /// Emergency pause mechanism
+        ///
+        /// Allows the authority to pause/unpause distributions in case of emergency.
+        ///
+        /// # Arguments
+        /// * `vault_id` - Unique identifier for this vault
+        /// * `paused` - Whether to pause (true) or unpause (false)
+        pub fn set_pause_state(
+            ctx: Context<SetPauseState>,
+            vault_id: [u8; 32],
+            paused: bool,
+        ) -> Result<()> {
+            instructions::set_pause_state::handler(ctx, vault_id, paused)
+        }
+
+        /// Change the creator payout wallet (admin only)
+        ///
+        /// Rejected while a day's distribution is part-way through.
+        ///
+        /// # Arguments
+        /// * `vault_id` - Unique identifier for this vault
+        pub fn update_creator(ctx: Context<UpdateCreator>, vault_id: [u8; 32]) -> Result<()> {
+            instructions::update_creator::handler(ctx, vault_id)
+        }
+
+        /// Propose a new policy authority (admin only)
+        ///
+        /// The transfer only takes effect once the proposed key accepts it.
+        ///
+        /// # Arguments
+        /// * `vault_id` - Unique identifier for this vault
+        /// * `new_authority` - Key that will become the authority on acceptance
+        pub fn propose_authority(
+            ctx: Context<ProposeAuthority>,
+            vault_id: [u8; 32],
+            new_authority: Pubkey,
+        ) -> Result<()> {
+            instructions::propose_authority::handler(ctx, vault_id, new_authority)
+        }
+
+        /// Accept a pending authority transfer
+        ///
+        /// Must be signed by the proposed authority.
+        ///
+        /// # Arguments
+        /// * `vault_id` - Unique identifier for this vault
+        pub fn accept_authority(ctx: Context<AcceptAuthority>, vault_id: [u8; 32]) -> Result<()> {
+            instructions::accept_authority::handler(ctx, vault_id)
+        }
 
-    pub fn add_liquidity_quote_only(
-        ctx: Context<AddLiquidityQuoteOnly>,
-        vault_id: [u8; 32],
-        liquidity_delta: u128,
-        token_a_amount_threshold: u64,
-        token_b_amount_threshold: u64,
-    ) -> Result<()> {
-        instructions::add_liquidity_quote_only::handler(
-            ctx,
-            vault_id,
-            liquidity_delta,
-            token_a_amount_threshold,
-            token_b_amount_threshold,
-        )
+        /// Cancel a pending authority transfer (admin only)
+        ///
+        /// # Arguments
+        /// * `vault_id` - Unique identifier for this vault
+        pub fn cancel_authority_proposal(
+            ctx: Context<CancelAuthorityProposal>,
+            vault_id: [u8; 32],
+        ) -> Result<()> {
+            instructions::cancel_authority_proposal::handler(ctx, vault_id)
+        }
     }
-    // / Main distribution crank - claims fees and distributes to investors
-    // /
-    // / This is the core function that should be called once per 24 hours.
-    // / It can be called multiple times with different page_numbers to process
-    // / all investors in a paginated manner.
-    // /
-    // / # Arguments
-    // / * `vault_id` - Unique identifier for this vault
-    // / * `page_number` - Current page being processed (0-indexed)
-    // /
-    // / # Remaining Accounts
-    // / For each investor on this page (up to 20):
-    // / - Streamflow stream account (read-only)
-    // / - Investor quote token account (writable)
-    // pub fn distribute_fees(
-    //     ctx: Context<DistributeFees>,
-    //     vault_id: [u8; 32],
-    //     page_number: u16,
-    // ) -> Result<()> {
-    //     instructions::distribute_fees::handler(ctx, vault_id, page_number)
-    // }
-
-    // / Finalize the day by sending remainder to creator
-    // /
-    // / Should be called after all pages have been processed.
-    // / Sends any remaining fees to the project creator.
-    // /
-    // / # Arguments
-    // / * `vault_id` - Unique identifier for this vault
-    // pub fn finalize_day_distribution(
-    //     ctx: Context<FinalizeDayDistribution>,
-    //     vault_id: [u8; 32],
-    // ) -> Result<()> {
-    //     instructions::finalize_day_distribution::handler(ctx, vault_id)
-    // }
-
-    // / Update policy configuration (admin only)
-    // /
-    // / Allows the authority to update distribution parameters.
-    // /
-    // / # Arguments
-    // / * `vault_id` - Unique identifier for this vault
-    // / * `investor_fee_share_bps` - New investor fee share (optional)
-    // / * `daily_cap_lamports` - New daily cap (optional)
-    // / * `min_payout_lamports` - New minimum payout (optional)
-    // pub fn update_policy(
-    //     ctx: Context<UpdatePolicy>,
-    //     vault_id: [u8; 32],
-    //     investor_fee_share_bps: Option<u16>,
-    //     daily_cap_lamports: Option<Option<u64>>,
-    //     min_payout_lamports: Option<u64>,
-    // ) -> Result<()> {
-    //     instructions::update_policy::handler(
-    //         ctx,
-    //         vault_id,
-    //         investor_fee_share_bps,
-    //         daily_cap_lamports,
-    //         min_payout_lamports,
-    //     )
-    // }
-
-    // / Emergency pause mechanism
-    // /
-    // / Allows the authority to pause/unpause distributions in case of emergency.
-    // /
-    // / # Arguments
-    // / * `vault_id` - Unique identifier for this vault
-    // / * `paused` - Whether to pause (true) or unpause (false)
-    // pub fn set_pause_state(
-    //     ctx: Context<SetPauseState>,
-    //     vault_id: [u8; 32],
-    //     paused: bool,
-    // ) -> Result<()> {
-    //     instructions::set_pause_state::handler(ctx, vault_id, paused)
-    // }
 }
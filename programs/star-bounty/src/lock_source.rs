@@ -0,0 +1,98 @@
+use crate::errors::StarInvestorFeesError;
+use crate::external_programs::{JUPITER_LOCK_PROGRAM_ID, STREAMFLOW_PROGRAM_ID};
+use crate::state::{InvestorLock, JupiterVestingEscrow, LockSourceKind, StreamflowStream};
+use crate::utils::validation;
+use anchor_lang::prelude::*;
+
+/// A vesting account locking base tokens for one investor
+pub trait LockSource {
+    /// Wallet entitled to the locked tokens
+    fn recipient(&self) -> Pubkey;
+
+    /// Mint of the locked tokens
+    fn mint(&self) -> Pubkey;
+
+    /// Amount still locked at the given timestamp
+    fn locked_amount(&self, timestamp: u64) -> u64;
+}
+
+impl LockSource for StreamflowStream {
+    fn recipient(&self) -> Pubkey {
+        self.recipient
+    }
+
+    fn mint(&self) -> Pubkey {
+        self.mint
+    }
+
+    fn locked_amount(&self, timestamp: u64) -> u64 {
+        self.calculate_locked_amount(timestamp)
+    }
+}
+
+impl LockSource for JupiterVestingEscrow {
+    fn recipient(&self) -> Pubkey {
+        self.recipient
+    }
+
+    fn mint(&self) -> Pubkey {
+        self.token_mint
+    }
+
+    fn locked_amount(&self, timestamp: u64) -> u64 {
+        self.calculate_locked_amount(timestamp)
+    }
+}
+
+impl LockSource for InvestorLock {
+    fn recipient(&self) -> Pubkey {
+        self.recipient
+    }
+
+    fn mint(&self) -> Pubkey {
+        self.mint
+    }
+
+    fn locked_amount(&self, timestamp: u64) -> u64 {
+        self.calculate_locked_amount(timestamp as i64)
+    }
+}
+
+impl LockSourceKind {
+    /// Program that owns this source's lock accounts
+    pub fn program_id(&self) -> Pubkey {
+        match self {
+            Self::Streamflow => STREAMFLOW_PROGRAM_ID,
+            Self::JupiterLock => JUPITER_LOCK_PROGRAM_ID,
+            Self::NativeEscrow => crate::ID,
+        }
+    }
+
+    /// Load the investor lock held in `account_info` for the vault `vault_id`
+    pub fn load(
+        &self,
+        account_info: &AccountInfo,
+        vault_id: &[u8; 32],
+    ) -> Result<Box<dyn LockSource>> {
+        require_keys_eq!(
+            *account_info.owner,
+            self.program_id(),
+            StarInvestorFeesError::InvalidLockAccount
+        );
+
+        let data = account_info.try_borrow_data()?;
+        let lock: Box<dyn LockSource> = match self {
+            Self::Streamflow => Box::new(StreamflowStream::parse(&data)?),
+            Self::JupiterLock => Box::new(JupiterVestingEscrow::parse(&data)?),
+            Self::NativeEscrow => {
+                let lock = InvestorLock::try_deserialize(&mut &data[..])
+                    .map_err(|_| StarInvestorFeesError::InvalidLockAccount)?;
+                // Escrows of every vault share this program as owner
+                validation::validate_vault_id(&lock.vault_id, vault_id)?;
+                Box::new(lock)
+            }
+        };
+
+        Ok(lock)
+    }
+}
@@ -1,3 +1,4 @@
+use crate::errors::StarInvestorFeesError;
 use anchor_lang::prelude::*;
 
 /// Policy configuration for fee distribution
@@ -9,6 +10,9 @@ pub struct PolicyConfig {
     /// Authority that can update policy
     pub authority: Pubkey,
 
+    /// Proposed new authority awaiting acceptance
+    pub pending_authority: Option<Pubkey>,
+
     /// Creator wallet to receive remainder fees
     pub creator: Pubkey,
 
@@ -27,12 +31,33 @@ pub struct PolicyConfig {
     /// Quote mint for the pool
     pub quote_mint: Pubkey,
 
+    /// Base (project) mint vested by investor streams
+    pub base_mint: Pubkey,
+
     /// Pool address
     pub pool: Pubkey,
 
     /// Honorary position address
     pub position: Pubkey,
 
+    /// Program whose accounts hold the investors' locked tokens
+    pub lock_source: LockSourceKind,
+
+    /// Number of investor registry pages (fixes the daily page count)
+    pub registry_pages: u16,
+
+    /// Seconds after a day starts before it may be finalized with payout pages pending
+    pub finalize_grace_seconds: i64,
+
+    /// Merkle root over (stream, investor_owner, weight_cap) leaves; zero selects the registry
+    pub investor_root: [u8; 32],
+
+    /// Number of leaves under `investor_root`
+    pub investor_count: u32,
+
+    /// Investors per crank page when `investor_root` is set
+    pub merkle_page_size: u16,
+
     /// Emergency pause flag
     pub paused: bool,
 
@@ -44,16 +69,57 @@ impl PolicyConfig {
     pub const LEN: usize = 8 + // discriminator
         32 + // vault_id
         32 + // authority
+        33 + // pending_authority (1 + 32)
         32 + // creator
         2 +  // investor_fee_share_bps
         9 +  // daily_cap_lamports (1 + 8)
         8 +  // min_payout_lamports
         8 +  // y0_total_allocation
         32 + // quote_mint
+        32 + // base_mint
         32 + // pool
         32 + // position
+        1 +  // lock_source
+        2 +  // registry_pages
+        8 +  // finalize_grace_seconds
+        32 + // investor_root
+        4 +  // investor_count
+        2 +  // merkle_page_size
         1 +  // paused
         1; // bump
+
+    /// Whether investors are proven against `investor_root` instead of the registry
+    pub fn uses_merkle_root(&self) -> bool {
+        self.investor_root != [0u8; 32]
+    }
+
+    /// Number of crank pages per day for the active investor source
+    pub fn day_pages(&self) -> u16 {
+        if self.uses_merkle_root() {
+            self.investor_count
+                .div_ceil(self.merkle_page_size.max(1) as u32) as u16
+        } else {
+            self.registry_pages
+        }
+    }
+
+    /// Number of investors expected on a Merkle crank page
+    pub fn merkle_page_len(&self, page_number: u16) -> usize {
+        let page_size = self.merkle_page_size as u32;
+        let covered = (page_number as u32).saturating_mul(page_size);
+        self.investor_count.saturating_sub(covered).min(page_size) as usize
+    }
+}
+
+/// Lock program a vault reads investors' locked amounts from
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LockSourceKind {
+    /// Streamflow `Contract` accounts
+    Streamflow,
+    /// Jupiter Lock `VestingEscrow` accounts
+    JupiterLock,
+    /// This program's own `InvestorLock` escrows
+    NativeEscrow,
 }
 
 /// Progress tracking for daily distribution
@@ -65,7 +131,7 @@ pub struct DistributionProgress {
     /// Timestamp of last distribution start
     pub last_distribution_ts: i64,
 
-    /// Current day start timestamp
+    /// Start of the current day (midnight UTC)
     pub current_day_start: i64,
 
     /// Total amount claimed from pool this day
@@ -83,9 +149,26 @@ pub struct DistributionProgress {
     /// Total pages to process this day
     pub total_pages: u16,
 
-    /// Carry-over dust from previous day
+    /// Sum of every investor's `InvestorAccrual` balance, held in the treasury
+    /// until paid (never paid to the creator)
     pub carry_over_dust: u64,
 
+    /// Part of today's investor pool moved into investor accruals
+    pub daily_dust: u64,
+
+    /// Sum of locked amounts across every page of the day (snapshot phase)
+    pub total_locked_snapshot: u64,
+
+    /// Number of pages included in the locked snapshot so far
+    pub snapshot_pages_done: u16,
+
+    /// Investor share of the day's fees, fixed when the first payout page claims
+    pub investor_fee_pool: u64,
+
+    /// Investor fees left unpaid by a day finalized after its grace window,
+    /// owed to investors through the next day's pool
+    pub carried_investor_fees: u64,
+
     /// Flag indicating if day is finalized
     pub day_finalized: bool,
 
@@ -104,24 +187,388 @@ impl DistributionProgress {
         2 +  // current_page
         2 +  // total_pages
         8 +  // carry_over_dust
+        8 +  // daily_dust
+        8 +  // total_locked_snapshot
+        2 +  // snapshot_pages_done
+        8 +  // investor_fee_pool
+        8 +  // carried_investor_fees
         1 +  // day_finalized
         1; // bump
 
-    pub fn reset_for_new_day(&mut self, timestamp: i64, claimed_amount: u64) {
+    pub fn reset_for_new_day(&mut self, timestamp: i64, total_pages: u16) {
         self.current_day_start = timestamp;
-        self.daily_claimed_amount = claimed_amount;
+        self.daily_claimed_amount = 0;
         self.daily_distributed_to_investors = 0;
         self.daily_distributed_to_creator = 0;
         self.current_page = 0;
-        self.total_pages = 0;
+        self.total_pages = total_pages;
+        self.daily_dust = 0;
+        self.total_locked_snapshot = 0;
+        self.snapshot_pages_done = 0;
+        // Carried fees are owed to investors even before today's claim
+        self.investor_fee_pool = self.owed_from_previous_days();
         self.day_finalized = false;
     }
 
+    /// Investor fees carried in from a day that could not assign its whole pool
+    pub fn owed_from_previous_days(&self) -> u64 {
+        self.carried_investor_fees
+    }
+
+    /// Part of today's investor pool that finalize carries into the next day
+    ///
+    /// Pending pages keep their investors' share. A completed day that assigned
+    /// nothing (no locked investors, or every share floored to zero) keeps the
+    /// whole pool, so fees owed from earlier days never fall to the creator.
+    pub fn investor_fees_to_carry(&self) -> u64 {
+        let assigned = self
+            .daily_distributed_to_investors
+            .saturating_add(self.daily_dust);
+        if self.is_payout_complete() && assigned > 0 {
+            return 0;
+        }
+        self.investor_fee_pool.saturating_sub(assigned)
+    }
+
+    /// Quote the treasury must hold for investors at any point
+    ///
+    /// Investor accruals plus, during a day, the part of the investor pool not
+    /// yet paid or accrued, or between days, the fees carried into the next one.
+    pub fn outstanding_investor_fees(&self) -> u64 {
+        let pool_outstanding = if self.is_day_in_progress() {
+            self.investor_fee_pool
+                .saturating_sub(self.daily_distributed_to_investors)
+                .saturating_sub(self.daily_dust)
+        } else {
+            self.owed_from_previous_days()
+        };
+        pool_outstanding.saturating_add(self.carry_over_dust)
+    }
+
+    /// Payout pages may only run once every page has been snapshotted
+    pub fn is_snapshot_complete(&self) -> bool {
+        self.total_pages > 0 && self.snapshot_pages_done == self.total_pages
+    }
+
+    /// Finalize may only run once every payout page of the day has been processed
+    pub fn is_payout_complete(&self) -> bool {
+        self.total_pages > 0 && self.current_page == self.total_pages
+    }
+
+    /// A day is in progress once its first page has claimed fees and until it is finalized
+    pub fn is_day_in_progress(&self) -> bool {
+        self.current_day_start != 0 && !self.day_finalized
+    }
+
+    /// A new day may open once `current_ts` falls in a later UTC day than the current one
+    ///
+    /// Days are anchored to the midnight boundary rather than the previous
+    /// run's execution time, so a scheduled crank that runs with less latency
+    /// than the day before is not rejected.
     pub fn is_new_day_ready(&self, current_ts: i64) -> bool {
-        current_ts >= self.last_distribution_ts + crate::constants::SECONDS_PER_DAY
+        crate::utils::time::get_day_start(current_ts) > self.current_day_start
     }
 }
 
+/// A registered investor: their vesting stream and payout wallet
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct RegistryEntry {
+    /// Streamflow stream for this investor
+    pub stream: Pubkey,
+
+    /// Wallet that owns the investor's quote token account
+    pub recipient: Pubkey,
+}
+
+/// Merkle membership proof for one investor on a crank page
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct InvestorProof {
+    /// Wallet that owns the investor's quote token account
+    pub investor_owner: Pubkey,
+
+    /// Maximum locked amount counted for this investor
+    pub weight_cap: u64,
+
+    /// Sibling hashes from the leaf up to the root
+    pub proof: Vec<[u8; 32]>,
+}
+
+/// One page of the investor registry; page N is processed as crank page N
+#[account]
+pub struct InvestorRegistryPage {
+    /// Vault ID for deterministic PDA derivation
+    pub vault_id: [u8; 32],
+
+    /// Index of this page
+    pub page_index: u16,
+
+    /// Registered investors, in crank order
+    pub entries: Vec<RegistryEntry>,
+
+    /// Bump for PDA derivation
+    pub bump: u8,
+}
+
+impl InvestorRegistryPage {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // vault_id
+        2 +  // page_index
+        4 + (64 * crate::constants::MAX_INVESTORS_PER_PAGE) + // entries
+        1; // bump
+
+    pub fn is_full(&self) -> bool {
+        self.entries.len() >= crate::constants::MAX_INVESTORS_PER_PAGE
+    }
+
+    pub fn find(&self, stream: &Pubkey) -> Option<usize> {
+        self.entries
+            .iter()
+            .position(|entry| entry.stream == *stream)
+    }
+}
+
+/// Marks a stream as registered, so it can sit on only one registry page
+#[account]
+pub struct InvestorMarker {
+    /// Vault ID for deterministic PDA derivation
+    pub vault_id: [u8; 32],
+
+    /// Registered stream
+    pub stream: Pubkey,
+
+    /// Registry page holding the stream
+    pub page_index: u16,
+
+    /// Bump for PDA derivation
+    pub bump: u8,
+}
+
+impl InvestorMarker {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // vault_id
+        32 + // stream
+        2 +  // page_index
+        1; // bump
+}
+
+/// Per-day receipt for an investor stream, blocking double snapshots and payouts
+#[account]
+pub struct InvestorReceipt {
+    /// Vault ID for deterministic PDA derivation
+    pub vault_id: [u8; 32],
+
+    /// Streamflow stream this receipt belongs to
+    pub stream: Pubkey,
+
+    /// Day start of the last snapshot that counted this stream
+    pub last_snapshot_day: i64,
+
+    /// Day start of the last payout page that processed this stream
+    pub last_paid_day: i64,
+
+    /// Capped locked amount counted by the last snapshot (the payout weight)
+    pub snapshot_locked_amount: u64,
+
+    /// Lock recipient at the last snapshot (must own the payout account)
+    pub recipient: Pubkey,
+
+    /// Cranker that paid the rent; refunded when the receipt is closed
+    pub rent_payer: Pubkey,
+
+    /// Bump for PDA derivation
+    pub bump: u8,
+}
+
+impl InvestorReceipt {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // vault_id
+        32 + // stream
+        8 +  // last_snapshot_day
+        8 +  // last_paid_day
+        8 +  // snapshot_locked_amount
+        32 + // recipient
+        32 + // rent_payer
+        1; // bump
+}
+
+/// Tuktuk cron job opening a vault's daily distribution
+#[account]
+pub struct CronConfig {
+    /// Vault ID for deterministic PDA derivation
+    pub vault_id: [u8; 32],
+
+    /// Cron job account (owned by the Tuktuk cron program)
+    pub cron_job: Pubkey,
+
+    /// Tuktuk task queue the job's tasks run on
+    pub task_queue: Pubkey,
+
+    /// Key that must sign the remote crank transactions
+    pub crank_signer: Pubkey,
+
+    /// Bump of the cron authority PDA that owns the job
+    pub authority_bump: u8,
+
+    /// Bump for PDA derivation
+    pub bump: u8,
+}
+
+impl CronConfig {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // vault_id
+        32 + // cron_job
+        32 + // task_queue
+        32 + // crank_signer
+        1 +  // authority_bump
+        1; // bump
+}
+
+/// Sub-minimum payouts owed to one investor stream, paid once they reach the minimum
+#[account]
+pub struct InvestorAccrual {
+    /// Vault ID for deterministic PDA derivation
+    pub vault_id: [u8; 32],
+
+    /// Investor stream this balance belongs to
+    pub stream: Pubkey,
+
+    /// Quote amount accrued and not yet paid (held in the treasury)
+    pub accrued: u64,
+
+    /// Cranker that paid the rent; refunded when the accrual is closed
+    pub rent_payer: Pubkey,
+
+    /// Bump for PDA derivation
+    pub bump: u8,
+}
+
+impl InvestorAccrual {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // vault_id
+        32 + // stream
+        8 +  // accrued
+        32 + // rent_payer
+        1; // bump
+}
+
+/// Program-owned escrow locking an investor's base tokens on a cliff/linear schedule
+///
+/// Nothing unlocks before `cliff_time`; `cliff_amount` unlocks at the cliff and
+/// the rest unlocks linearly until `end_time`. A pure cliff has
+/// `cliff_time == end_time`, a pure linear lock `cliff_time == start_time`.
+#[account]
+pub struct InvestorLock {
+    /// Vault ID for deterministic PDA derivation
+    pub vault_id: [u8; 32],
+
+    /// Wallet entitled to the locked tokens
+    pub recipient: Pubkey,
+
+    /// Mint of the locked tokens (the policy's base mint)
+    pub mint: Pubkey,
+
+    /// Total tokens deposited into the escrow
+    pub total_amount: u64,
+
+    /// Tokens the recipient has withdrawn
+    pub withdrawn_amount: u64,
+
+    /// Schedule start timestamp
+    pub start_time: i64,
+
+    /// Cliff timestamp
+    pub cliff_time: i64,
+
+    /// Timestamp at which everything is unlocked
+    pub end_time: i64,
+
+    /// Tokens unlocked at the cliff
+    pub cliff_amount: u64,
+
+    /// Bump for PDA derivation
+    pub bump: u8,
+
+    /// Bump of the escrow token account
+    pub escrow_bump: u8,
+}
+
+impl InvestorLock {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // vault_id
+        32 + // recipient
+        32 + // mint
+        8 +  // total_amount
+        8 +  // withdrawn_amount
+        8 +  // start_time
+        8 +  // cliff_time
+        8 +  // end_time
+        8 +  // cliff_amount
+        1 +  // bump
+        1; // escrow_bump
+
+    /// Whether the schedule is well formed
+    pub fn is_valid_schedule(&self) -> bool {
+        self.total_amount > 0
+            && self.start_time <= self.cliff_time
+            && self.cliff_time <= self.end_time
+            && self.cliff_amount <= self.total_amount
+    }
+
+    /// Amount unlocked by the given timestamp, withdrawn or not
+    pub fn unlocked_amount(&self, current_time: i64) -> u64 {
+        if current_time < self.cliff_time {
+            return 0;
+        }
+        if current_time >= self.end_time {
+            return self.total_amount;
+        }
+
+        // Strictly between cliff and end, so the duration is non-zero
+        let linear_total = self.total_amount.saturating_sub(self.cliff_amount);
+        let elapsed = (current_time - self.cliff_time) as u128;
+        let duration = (self.end_time - self.cliff_time) as u128;
+        let linear_unlocked = (linear_total as u128 * elapsed / duration) as u64;
+
+        self.cliff_amount.saturating_add(linear_unlocked)
+    }
+
+    /// Unlocked amount the recipient has not withdrawn yet
+    pub fn available_to_withdraw(&self, current_time: i64) -> u64 {
+        self.unlocked_amount(current_time)
+            .saturating_sub(self.withdrawn_amount)
+    }
+
+    /// Calculate the still-locked amount at the given timestamp
+    pub fn calculate_locked_amount(&self, current_time: i64) -> u64 {
+        let released = self
+            .unlocked_amount(current_time)
+            .max(self.withdrawn_amount);
+
+        self.total_amount.saturating_sub(released)
+    }
+
+    /// Snapshot of the lock's balances at the given timestamp
+    pub fn status(&self, current_time: i64) -> InvestorLockStatus {
+        InvestorLockStatus {
+            total_amount: self.total_amount,
+            unlocked_amount: self.unlocked_amount(current_time),
+            withdrawn_amount: self.withdrawn_amount,
+            locked_amount: self.calculate_locked_amount(current_time),
+            available_to_withdraw: self.available_to_withdraw(current_time),
+        }
+    }
+}
+
+/// Balances of an `InvestorLock`, returned by the query instruction
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct InvestorLockStatus {
+    pub total_amount: u64,
+    pub unlocked_amount: u64,
+    pub withdrawn_amount: u64,
+    pub locked_amount: u64,
+    pub available_to_withdraw: u64,
+}
+
 /// Investor input for distribution (passed as remaining accounts)
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct InvestorInput {
@@ -132,42 +579,882 @@ pub struct InvestorInput {
     pub streamflow_stream: Pubkey,
 }
 
-/// Streamflow stream data structure (simplified - only what we need)
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+/// Byte offsets of the fields we read from a Streamflow `Contract` account
+///
+/// The account is Borsh-encoded without an Anchor discriminator. Everything up
+/// to the end of `CreateParams` is fixed size; `closed` follows the
+/// variable-length `ix_padding` vector.
+pub mod streamflow_layout {
+    pub const MAGIC: usize = 0;
+    pub const AMOUNT_WITHDRAWN: usize = 17;
+    pub const CANCELED_AT: usize = 25;
+    pub const RECIPIENT: usize = 113;
+    pub const MINT: usize = 177;
+    pub const START_TIME: usize = 409;
+    pub const NET_AMOUNT_DEPOSITED: usize = 417;
+    pub const PERIOD: usize = 425;
+    pub const AMOUNT_PER_PERIOD: usize = 433;
+    pub const CLIFF: usize = 441;
+    pub const CLIFF_AMOUNT: usize = 449;
+    /// End of `CreateParams`, where the `ix_padding` length prefix starts
+    pub const IX_PADDING: usize = 541;
+}
+
+/// Fields of a Streamflow `Contract` needed to value an investor's lock
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
 pub struct StreamflowStream {
-    pub amount: u64,
-    pub withdrawn: u64,
+    /// Tokens released every `period` seconds after the cliff
+    pub amount_per_period: u64,
+    /// Release period in seconds
+    pub period: u64,
+    /// Tokens released at the cliff
+    pub cliff_amount: u64,
+    /// Stream start timestamp
     pub start_time: u64,
-    pub end_time: u64,
+    /// Cliff timestamp
     pub cliff: u64,
+    /// Tokens deposited into the stream, net of fees
+    pub net_amount_deposited: u64,
+    /// Tokens the recipient has already withdrawn
+    pub amount_withdrawn: u64,
+    /// Whether the stream has been closed
+    pub closed: bool,
+    /// Cancellation timestamp (0 if never cancelled)
+    pub canceled_at: u64,
+    /// Wallet entitled to the streamed tokens
+    pub recipient: Pubkey,
+    /// Mint of the streamed tokens
+    pub mint: Pubkey,
 }
 
 impl StreamflowStream {
-    /// Calculate currently locked amount at given timestamp
+    /// Parse the raw data of a Streamflow `Contract` account
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        use streamflow_layout::*;
+
+        let read_u64 = |offset: usize| -> Result<u64> {
+            data.get(offset..offset + 8)
+                .and_then(|bytes| bytes.try_into().ok())
+                .map(u64::from_le_bytes)
+                .ok_or_else(|| StarInvestorFeesError::StreamflowDeserializationFailed.into())
+        };
+        let read_pubkey = |offset: usize| -> Result<Pubkey> {
+            data.get(offset..offset + 32)
+                .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+                .map(Pubkey::new_from_array)
+                .ok_or_else(|| StarInvestorFeesError::StreamflowDeserializationFailed.into())
+        };
+
+        // An all-zero magic means the account was never initialized
+        require!(
+            read_u64(MAGIC)? != 0,
+            StarInvestorFeesError::InvalidStreamflowAccount
+        );
+
+        // `closed` sits right after the Borsh-encoded `ix_padding: Vec<u8>`
+        let padding_len = data
+            .get(IX_PADDING..IX_PADDING + 4)
+            .and_then(|bytes| bytes.try_into().ok())
+            .map(u32::from_le_bytes)
+            .ok_or(StarInvestorFeesError::StreamflowDeserializationFailed)?;
+        let closed_offset = IX_PADDING + 4 + padding_len as usize;
+        let closed = match data.get(closed_offset) {
+            Some(0) => false,
+            Some(1) => true,
+            _ => return Err(StarInvestorFeesError::StreamflowDeserializationFailed.into()),
+        };
+
+        Ok(Self {
+            amount_per_period: read_u64(AMOUNT_PER_PERIOD)?,
+            period: read_u64(PERIOD)?,
+            cliff_amount: read_u64(CLIFF_AMOUNT)?,
+            start_time: read_u64(START_TIME)?,
+            cliff: read_u64(CLIFF)?,
+            net_amount_deposited: read_u64(NET_AMOUNT_DEPOSITED)?,
+            amount_withdrawn: read_u64(AMOUNT_WITHDRAWN)?,
+            closed,
+            canceled_at: read_u64(CANCELED_AT)?,
+            recipient: read_pubkey(RECIPIENT)?,
+            mint: read_pubkey(MINT)?,
+        })
+    }
+
+    /// Timestamp from which the cliff amount and periodic releases count
+    fn unlock_start(&self) -> u64 {
+        self.start_time.max(self.cliff)
+    }
+
+    /// Amount vested (unlocked) by the given timestamp, withdrawn or not
+    ///
+    /// Mirrors Streamflow: nothing before the cliff, then `cliff_amount` plus
+    /// `amount_per_period` for every full period elapsed since the cliff,
+    /// capped at the net deposit.
+    pub fn unlocked_amount(&self, current_time: u64) -> u64 {
+        if current_time < self.unlock_start() {
+            return 0;
+        }
+
+        // A zero period is invalid in Streamflow; only the cliff amount is released
+        let periods = (current_time - self.unlock_start())
+            .checked_div(self.period)
+            .unwrap_or(0);
+
+        periods
+            .saturating_mul(self.amount_per_period)
+            .saturating_add(self.cliff_amount)
+            .min(self.net_amount_deposited)
+    }
+
+    /// Unlocked amount the recipient has not withdrawn yet
+    pub fn available_to_withdraw(&self, current_time: u64) -> u64 {
+        self.unlocked_amount(current_time)
+            .saturating_sub(self.amount_withdrawn)
+    }
+
+    /// Whether the stream no longer holds tokens for the recipient at the given timestamp
+    pub fn is_terminated(&self, current_time: u64) -> bool {
+        self.closed || (self.canceled_at != 0 && current_time >= self.canceled_at)
+    }
+
+    /// Calculate the still-locked amount at the given timestamp
+    ///
+    /// Locked means not yet vested: unlocked-but-unwithdrawn tokens are not
+    /// locked. Cancelled and closed streams lock nothing.
     pub fn calculate_locked_amount(&self, current_time: u64) -> u64 {
-        if current_time < self.start_time {
-            return self.amount;
+        if self.is_terminated(current_time) {
+            return 0;
         }
 
-        if current_time >= self.end_time {
+        // Withdrawn tokens have always vested, even if the schedule lags behind
+        let released = self
+            .unlocked_amount(current_time)
+            .max(self.amount_withdrawn);
+
+        self.net_amount_deposited.saturating_sub(released)
+    }
+}
+
+/// Byte offsets of the fields we read from a Jupiter Lock `VestingEscrow` account
+pub mod jupiter_lock_layout {
+    /// Anchor discriminator: sha256("account:VestingEscrow")[..8]
+    pub const DISCRIMINATOR: [u8; 8] = [244, 119, 183, 4, 73, 116, 135, 195];
+    pub const RECIPIENT: usize = 8;
+    pub const TOKEN_MINT: usize = 40;
+    pub const CLIFF_TIME: usize = 144;
+    pub const FREQUENCY: usize = 152;
+    pub const CLIFF_UNLOCK_AMOUNT: usize = 160;
+    pub const AMOUNT_PER_PERIOD: usize = 168;
+    pub const NUMBER_OF_PERIOD: usize = 176;
+    pub const TOTAL_CLAIMED_AMOUNT: usize = 184;
+    pub const VESTING_START_TIME: usize = 192;
+    pub const CANCELLED_AT: usize = 200;
+    /// Minimum account size covering every field we read
+    pub const MIN_LEN: usize = 208;
+}
+
+/// Fields of a Jupiter Lock `VestingEscrow` needed to value an investor's lock
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct JupiterVestingEscrow {
+    /// Wallet entitled to the vested tokens
+    pub recipient: Pubkey,
+    /// Mint of the vested tokens
+    pub token_mint: Pubkey,
+    /// Timestamp at which the cliff amount unlocks
+    pub cliff_time: u64,
+    /// Seconds between period unlocks
+    pub frequency: u64,
+    /// Tokens unlocked at the cliff
+    pub cliff_unlock_amount: u64,
+    /// Tokens unlocked every period
+    pub amount_per_period: u64,
+    /// Number of periods after the cliff
+    pub number_of_period: u64,
+    /// Tokens the recipient has already claimed
+    pub total_claimed_amount: u64,
+    /// Vesting start timestamp
+    pub vesting_start_time: u64,
+    /// Cancellation timestamp (0 if never cancelled)
+    pub cancelled_at: u64,
+}
+
+impl JupiterVestingEscrow {
+    /// Parse the raw data of a Jupiter Lock `VestingEscrow` account
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        use jupiter_lock_layout::*;
+
+        require!(
+            data.len() >= MIN_LEN && data[..8] == DISCRIMINATOR,
+            StarInvestorFeesError::InvalidLockAccount
+        );
+
+        let read_u64 =
+            |offset: usize| u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
+        let read_pubkey =
+            |offset: usize| Pubkey::new_from_array(data[offset..offset + 32].try_into().unwrap());
+
+        Ok(Self {
+            recipient: read_pubkey(RECIPIENT),
+            token_mint: read_pubkey(TOKEN_MINT),
+            cliff_time: read_u64(CLIFF_TIME),
+            frequency: read_u64(FREQUENCY),
+            cliff_unlock_amount: read_u64(CLIFF_UNLOCK_AMOUNT),
+            amount_per_period: read_u64(AMOUNT_PER_PERIOD),
+            number_of_period: read_u64(NUMBER_OF_PERIOD),
+            total_claimed_amount: read_u64(TOTAL_CLAIMED_AMOUNT),
+            vesting_start_time: read_u64(VESTING_START_TIME),
+            cancelled_at: read_u64(CANCELLED_AT),
+        })
+    }
+
+    /// Total tokens the escrow vests
+    pub fn total_deposit(&self) -> u64 {
+        self.amount_per_period
+            .saturating_mul(self.number_of_period)
+            .saturating_add(self.cliff_unlock_amount)
+    }
+
+    /// Amount unlocked by the given timestamp, mirroring Jupiter Lock
+    pub fn unlocked_amount(&self, current_time: u64) -> u64 {
+        if current_time < self.cliff_time {
             return 0;
         }
 
-        let elapsed = current_time.saturating_sub(self.start_time);
-        let total_duration = self.end_time.saturating_sub(self.start_time);
+        let periods = (current_time - self.cliff_time)
+            .checked_div(self.frequency)
+            .unwrap_or(0)
+            .min(self.number_of_period);
+
+        periods
+            .saturating_mul(self.amount_per_period)
+            .saturating_add(self.cliff_unlock_amount)
+    }
 
-        if elapsed < self.cliff {
-            return self.amount;
+    /// Calculate the still-locked amount at the given timestamp
+    pub fn calculate_locked_amount(&self, current_time: u64) -> u64 {
+        if self.cancelled_at != 0 && current_time >= self.cancelled_at {
+            return 0;
         }
 
-        let unlocked = self
-            .amount
-            .checked_mul(elapsed)
-            .and_then(|v| v.checked_div(total_duration))
-            .unwrap_or(0);
+        let released = self
+            .unlocked_amount(current_time)
+            .max(self.total_claimed_amount);
+
+        self.total_deposit().saturating_sub(released)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Field values of a Streamflow `Contract` fixture
+    struct ContractFixture {
+        amount_withdrawn: u64,
+        canceled_at: u64,
+        recipient: Pubkey,
+        mint: Pubkey,
+        start_time: u64,
+        net_amount_deposited: u64,
+        period: u64,
+        amount_per_period: u64,
+        cliff: u64,
+        cliff_amount: u64,
+        ix_padding: Vec<u8>,
+        closed: bool,
+    }
+
+    impl ContractFixture {
+        fn new() -> Self {
+            Self {
+                amount_withdrawn: 25_000,
+                canceled_at: 0,
+                recipient: Pubkey::new_unique(),
+                mint: Pubkey::new_unique(),
+                start_time: 1_700_000_000,
+                net_amount_deposited: 1_000_000,
+                period: 86_400,
+                amount_per_period: 10_000,
+                cliff: 1_700_000_000,
+                cliff_amount: 100_000,
+                ix_padding: vec![0; 126],
+                closed: false,
+            }
+        }
+
+        /// Borsh-encode the full `Contract` struct field by field
+        fn encode(&self) -> Vec<u8> {
+            let mut data = Vec::new();
+            let u64_field =
+                |data: &mut Vec<u8>, value: u64| data.extend_from_slice(&value.to_le_bytes());
+            let filler = Pubkey::new_unique();
+
+            u64_field(&mut data, 0x5354_524d_464c_4f57); // magic
+            data.push(2); // version
+            u64_field(&mut data, 1_699_999_000); // created_at
+            u64_field(&mut data, self.amount_withdrawn);
+            u64_field(&mut data, self.canceled_at);
+            u64_field(&mut data, 1_800_000_000); // end_time
+            u64_field(&mut data, 1_700_100_000); // last_withdrawn_at
+            data.extend_from_slice(filler.as_ref()); // sender
+            data.extend_from_slice(filler.as_ref()); // sender_tokens
+            data.extend_from_slice(self.recipient.as_ref());
+            data.extend_from_slice(filler.as_ref()); // recipient_tokens
+            data.extend_from_slice(self.mint.as_ref());
+            data.extend_from_slice(filler.as_ref()); // escrow_tokens
+            data.extend_from_slice(filler.as_ref()); // streamflow_treasury
+            data.extend_from_slice(filler.as_ref()); // streamflow_treasury_tokens
+            u64_field(&mut data, 2_500); // streamflow_fee_total
+            u64_field(&mut data, 0); // streamflow_fee_withdrawn
+            data.extend_from_slice(&0.25f32.to_le_bytes()); // streamflow_fee_percent
+            data.extend_from_slice(filler.as_ref()); // partner
+            data.extend_from_slice(filler.as_ref()); // partner_tokens
+            u64_field(&mut data, 0); // partner_fee_total
+            u64_field(&mut data, 0); // partner_fee_withdrawn
+            data.extend_from_slice(&0f32.to_le_bytes()); // partner_fee_percent
+
+            // CreateParams
+            u64_field(&mut data, self.start_time);
+            u64_field(&mut data, self.net_amount_deposited);
+            u64_field(&mut data, self.period);
+            u64_field(&mut data, self.amount_per_period);
+            u64_field(&mut data, self.cliff);
+            u64_field(&mut data, self.cliff_amount);
+            data.extend_from_slice(&[1, 0, 0, 0, 1, 0]); // permission flags
+            data.extend_from_slice(&[b'x'; 64]); // stream_name
+            u64_field(&mut data, 86_400); // withdraw_frequency
+            data.extend_from_slice(&0u32.to_le_bytes()); // ghost
+            data.extend_from_slice(&[0, 0]); // pausable, can_update_rate
+
+            data.extend_from_slice(&(self.ix_padding.len() as u32).to_le_bytes());
+            data.extend_from_slice(&self.ix_padding);
+            data.push(self.closed as u8);
+            u64_field(&mut data, 0); // current_pause_start
+            u64_field(&mut data, 0); // pause_cumulative
+            u64_field(&mut data, 0); // last_rate_change_time
+            u64_field(&mut data, 0); // funds_unlocked_at_last_rate_change
+            data
+        }
+    }
+
+    #[test]
+    fn test_streamflow_layout_offsets() {
+        let fixture = ContractFixture::new();
+        let data = fixture.encode();
+
+        assert_eq!(
+            &data[streamflow_layout::RECIPIENT..streamflow_layout::RECIPIENT + 32],
+            fixture.recipient.as_ref()
+        );
+        assert_eq!(
+            u32::from_le_bytes(
+                data[streamflow_layout::IX_PADDING..streamflow_layout::IX_PADDING + 4]
+                    .try_into()
+                    .unwrap()
+            ),
+            126
+        );
+    }
+
+    #[test]
+    fn test_parse_streamflow_contract() {
+        let fixture = ContractFixture::new();
+        let stream = StreamflowStream::parse(&fixture.encode()).unwrap();
+
+        assert_eq!(
+            stream,
+            StreamflowStream {
+                amount_per_period: 10_000,
+                period: 86_400,
+                cliff_amount: 100_000,
+                start_time: 1_700_000_000,
+                cliff: 1_700_000_000,
+                net_amount_deposited: 1_000_000,
+                amount_withdrawn: 25_000,
+                closed: false,
+                canceled_at: 0,
+                recipient: fixture.recipient,
+                mint: fixture.mint,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_streamflow_closed_and_cancelled() {
+        let mut fixture = ContractFixture::new();
+        fixture.closed = true;
+        fixture.canceled_at = 1_700_500_000;
+        fixture.ix_padding = vec![7; 3];
+
+        let stream = StreamflowStream::parse(&fixture.encode()).unwrap();
+        assert!(stream.closed);
+        assert_eq!(stream.canceled_at, 1_700_500_000);
+    }
+
+    #[test]
+    fn test_parse_streamflow_rejects_bad_data() {
+        let data = ContractFixture::new().encode();
+
+        // Truncated before `closed`
+        assert!(StreamflowStream::parse(&data[..streamflow_layout::IX_PADDING + 4]).is_err());
+        // Uninitialized account
+        assert!(StreamflowStream::parse(&[0u8; 1104]).is_err());
+        // Invalid bool for `closed`
+        let mut corrupted = data.clone();
+        corrupted[streamflow_layout::IX_PADDING + 4 + 126] = 2;
+        assert!(StreamflowStream::parse(&corrupted).is_err());
+    }
+
+    #[test]
+    fn test_locked_amount_from_fixture() {
+        let stream = StreamflowStream::parse(&ContractFixture::new().encode()).unwrap();
+
+        // Before start everything is locked, withdrawals aside
+        assert_eq!(stream.calculate_locked_amount(1_699_000_000), 975_000);
+        // Cliff releases 100k, then 10k per day
+        assert_eq!(stream.calculate_locked_amount(1_700_000_000), 900_000);
+        assert_eq!(
+            stream.calculate_locked_amount(1_700_000_000 + 3 * 86_400 + 1),
+            870_000
+        );
+        // Fully unlocked after 90 periods
+        assert_eq!(
+            stream.calculate_locked_amount(1_700_000_000 + 90 * 86_400),
+            0
+        );
+    }
+
+    fn vesting_stream() -> StreamflowStream {
+        StreamflowStream {
+            amount_per_period: 100,
+            period: 10,
+            cliff_amount: 250,
+            start_time: 1_000,
+            cliff: 1_100,
+            net_amount_deposited: 1_250,
+            amount_withdrawn: 0,
+            closed: false,
+            canceled_at: 0,
+            recipient: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+        }
+    }
+
+    #[test]
+    fn test_vesting_before_cliff() {
+        let stream = vesting_stream();
+
+        for t in [0, 999, 1_000, 1_050, 1_099] {
+            assert_eq!(stream.unlocked_amount(t), 0);
+            assert_eq!(stream.calculate_locked_amount(t), 1_250);
+        }
+    }
+
+    #[test]
+    fn test_vesting_cliff_amount_released_at_cliff() {
+        let stream = vesting_stream();
+
+        assert_eq!(stream.unlocked_amount(1_100), 250);
+        assert_eq!(stream.calculate_locked_amount(1_100), 1_000);
+    }
+
+    #[test]
+    fn test_vesting_discrete_periods() {
+        let stream = vesting_stream();
+
+        // Partial periods release nothing extra
+        assert_eq!(stream.unlocked_amount(1_109), 250);
+        assert_eq!(stream.unlocked_amount(1_110), 350);
+        assert_eq!(stream.unlocked_amount(1_119), 350);
+        assert_eq!(stream.unlocked_amount(1_120), 450);
+
+        for periods in 0..=10u64 {
+            let t = 1_100 + periods * 10;
+            let expected = (250 + periods * 100).min(1_250);
+            assert_eq!(stream.unlocked_amount(t), expected);
+            assert_eq!(stream.calculate_locked_amount(t), 1_250 - expected);
+        }
+    }
+
+    #[test]
+    fn test_vesting_capped_at_deposit() {
+        let stream = vesting_stream();
+
+        assert_eq!(stream.unlocked_amount(1_200), 1_250);
+        assert_eq!(stream.unlocked_amount(u64::MAX), 1_250);
+        assert_eq!(stream.calculate_locked_amount(u64::MAX), 0);
+    }
+
+    #[test]
+    fn test_vesting_without_cliff_starts_at_start_time() {
+        let mut stream = vesting_stream();
+        stream.cliff = 0;
+        stream.cliff_amount = 0;
+
+        assert_eq!(stream.unlocked_amount(999), 0);
+        assert_eq!(stream.unlocked_amount(1_000), 0);
+        assert_eq!(stream.unlocked_amount(1_010), 100);
+        assert_eq!(stream.calculate_locked_amount(1_035), 950);
+    }
+
+    #[test]
+    fn test_vesting_zero_period_only_releases_cliff() {
+        let mut stream = vesting_stream();
+        stream.period = 0;
+
+        assert_eq!(stream.unlocked_amount(1_100), 250);
+        assert_eq!(stream.unlocked_amount(9_999), 250);
+    }
+
+    #[test]
+    fn test_vesting_large_amounts_saturate() {
+        let mut stream = vesting_stream();
+        stream.amount_per_period = u64::MAX;
+        stream.net_amount_deposited = u64::MAX;
+
+        assert_eq!(stream.unlocked_amount(1_100 + 30), u64::MAX);
+        assert_eq!(stream.calculate_locked_amount(1_100 + 30), 0);
+    }
+
+    #[test]
+    fn test_unlocked_but_not_withdrawn_is_not_locked() {
+        let mut stream = vesting_stream();
+        stream.amount_withdrawn = 300;
+
+        // 450 unlocked at t=1120: 300 withdrawn, 150 still claimable
+        assert_eq!(stream.available_to_withdraw(1_120), 150);
+        assert_eq!(stream.calculate_locked_amount(1_120), 800);
+
+        // Withdrawals never count twice against the locked amount
+        stream.amount_withdrawn = 450;
+        assert_eq!(stream.available_to_withdraw(1_120), 0);
+        assert_eq!(stream.calculate_locked_amount(1_120), 800);
+    }
+
+    #[test]
+    fn test_withdrawn_ahead_of_schedule_is_not_locked() {
+        let mut stream = vesting_stream();
+        stream.amount_withdrawn = 600;
+
+        assert_eq!(stream.available_to_withdraw(1_120), 0);
+        assert_eq!(stream.calculate_locked_amount(1_120), 650);
+    }
+
+    #[test]
+    fn test_cancelled_stream_locks_nothing_after_cancellation() {
+        let mut stream = vesting_stream();
+        stream.canceled_at = 1_150;
+
+        // Before cancellation the schedule still applies
+        assert_eq!(stream.calculate_locked_amount(1_140), 600);
+        assert!(!stream.is_terminated(1_149));
+
+        assert!(stream.is_terminated(1_150));
+        assert_eq!(stream.calculate_locked_amount(1_150), 0);
+        assert_eq!(stream.calculate_locked_amount(1_000), 1_250);
+    }
+
+    #[test]
+    fn test_closed_stream_locks_nothing() {
+        let mut stream = vesting_stream();
+        stream.closed = true;
+
+        for t in [0, 1_100, 1_150, u64::MAX] {
+            assert!(stream.is_terminated(t));
+            assert_eq!(stream.calculate_locked_amount(t), 0);
+        }
+    }
+
+    fn jupiter_fixture(escrow: &JupiterVestingEscrow) -> Vec<u8> {
+        // Anchor zero-copy layout of `VestingEscrow`
+        let mut data = jupiter_lock_layout::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(escrow.recipient.as_ref());
+        data.extend_from_slice(escrow.token_mint.as_ref());
+        data.extend_from_slice(Pubkey::new_unique().as_ref()); // creator
+        data.extend_from_slice(Pubkey::new_unique().as_ref()); // base
+        data.extend_from_slice(&[254, 0, 1, 0]); // bump, update/cancel modes, token flag
+        data.extend_from_slice(&[0; 4]); // padding_0
+        for value in [
+            escrow.cliff_time,
+            escrow.frequency,
+            escrow.cliff_unlock_amount,
+            escrow.amount_per_period,
+            escrow.number_of_period,
+            escrow.total_claimed_amount,
+            escrow.vesting_start_time,
+            escrow.cancelled_at,
+            0, // padding_1
+        ] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(&[0; 80]); // buffer
+        data
+    }
+
+    fn jupiter_escrow() -> JupiterVestingEscrow {
+        JupiterVestingEscrow {
+            recipient: Pubkey::new_unique(),
+            token_mint: Pubkey::new_unique(),
+            cliff_time: 2_000,
+            frequency: 100,
+            cliff_unlock_amount: 400,
+            amount_per_period: 60,
+            number_of_period: 10,
+            total_claimed_amount: 0,
+            vesting_start_time: 1_000,
+            cancelled_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_parse_jupiter_vesting_escrow() {
+        let escrow = jupiter_escrow();
+        let data = jupiter_fixture(&escrow);
+
+        assert_eq!(JupiterVestingEscrow::parse(&data).unwrap(), escrow);
+
+        let mut wrong_discriminator = data.clone();
+        wrong_discriminator[0] ^= 1;
+        assert!(JupiterVestingEscrow::parse(&wrong_discriminator).is_err());
+        assert!(JupiterVestingEscrow::parse(&data[..jupiter_lock_layout::MIN_LEN - 1]).is_err());
+    }
+
+    #[test]
+    fn test_jupiter_locked_amount() {
+        let mut escrow = jupiter_escrow();
+        assert_eq!(escrow.total_deposit(), 1_000);
+
+        assert_eq!(escrow.calculate_locked_amount(1_999), 1_000);
+        assert_eq!(escrow.calculate_locked_amount(2_000), 600);
+        assert_eq!(escrow.calculate_locked_amount(2_250), 480);
+        // Unlocks stop after the last period
+        assert_eq!(escrow.calculate_locked_amount(2_000 + 10 * 100), 0);
+        assert_eq!(escrow.unlocked_amount(u64::MAX), 1_000);
+
+        escrow.total_claimed_amount = 700;
+        assert_eq!(escrow.calculate_locked_amount(2_250), 300);
+
+        escrow.cancelled_at = 2_300;
+        assert_eq!(escrow.calculate_locked_amount(2_300), 0);
+    }
+
+    fn investor_lock() -> InvestorLock {
+        InvestorLock {
+            vault_id: [1u8; 32],
+            recipient: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            total_amount: 1_000,
+            withdrawn_amount: 0,
+            start_time: 100,
+            cliff_time: 200,
+            end_time: 1_000,
+            cliff_amount: 200,
+            bump: 255,
+            escrow_bump: 254,
+        }
+    }
+
+    #[test]
+    fn test_investor_lock_cliff_then_linear() {
+        let lock = investor_lock();
+        assert!(lock.is_valid_schedule());
+
+        assert_eq!(lock.unlocked_amount(0), 0);
+        assert_eq!(lock.unlocked_amount(199), 0);
+        assert_eq!(lock.unlocked_amount(200), 200);
+        assert_eq!(lock.unlocked_amount(600), 600);
+        assert_eq!(lock.unlocked_amount(999), 999);
+        assert_eq!(lock.unlocked_amount(1_000), 1_000);
+        assert_eq!(lock.calculate_locked_amount(600), 400);
+    }
+
+    #[test]
+    fn test_investor_lock_pure_cliff_and_pure_linear() {
+        let mut cliff = investor_lock();
+        cliff.cliff_time = 500;
+        cliff.end_time = 500;
+        cliff.cliff_amount = 0;
+        assert!(cliff.is_valid_schedule());
+        assert_eq!(cliff.calculate_locked_amount(499), 1_000);
+        assert_eq!(cliff.calculate_locked_amount(500), 0);
+
+        let mut linear = investor_lock();
+        linear.cliff_time = linear.start_time;
+        linear.cliff_amount = 0;
+        assert_eq!(linear.unlocked_amount(100), 0);
+        assert_eq!(linear.unlocked_amount(550), 500);
+    }
+
+    #[test]
+    fn test_investor_lock_withdrawals() {
+        let mut lock = investor_lock();
+        lock.withdrawn_amount = 500;
+
+        let status = lock.status(600);
+        assert_eq!(
+            status,
+            InvestorLockStatus {
+                total_amount: 1_000,
+                unlocked_amount: 600,
+                withdrawn_amount: 500,
+                locked_amount: 400,
+                available_to_withdraw: 100,
+            }
+        );
+    }
+
+    #[test]
+    fn test_investor_lock_invalid_schedules() {
+        let mut lock = investor_lock();
+        lock.cliff_time = 50;
+        assert!(!lock.is_valid_schedule());
+
+        let mut lock = investor_lock();
+        lock.end_time = 150;
+        assert!(!lock.is_valid_schedule());
+
+        let mut lock = investor_lock();
+        lock.cliff_amount = 1_001;
+        assert!(!lock.is_valid_schedule());
+
+        let mut lock = investor_lock();
+        lock.total_amount = 0;
+        lock.cliff_amount = 0;
+        assert!(!lock.is_valid_schedule());
+    }
+
+    #[test]
+    fn test_progress_payout_complete() {
+        let mut progress = DistributionProgress {
+            vault_id: [1u8; 32],
+            last_distribution_ts: 0,
+            current_day_start: 0,
+            daily_claimed_amount: 0,
+            daily_distributed_to_investors: 0,
+            daily_distributed_to_creator: 0,
+            current_page: 0,
+            total_pages: 0,
+            carry_over_dust: 0,
+            daily_dust: 0,
+            total_locked_snapshot: 0,
+            snapshot_pages_done: 0,
+            investor_fee_pool: 0,
+            carried_investor_fees: 0,
+            day_finalized: false,
+            bump: 255,
+        };
+        // Nothing to finalize before a day has started
+        assert!(!progress.is_payout_complete());
+
+        progress.reset_for_new_day(1_700_000_000, 3);
+        assert!(!progress.is_payout_complete());
+
+        progress.current_page = 2;
+        assert!(!progress.is_payout_complete());
+
+        progress.current_page = 3;
+        assert!(progress.is_payout_complete());
+    }
+
+    #[test]
+    fn test_outstanding_investor_fees() {
+        let mut progress = DistributionProgress {
+            vault_id: [1u8; 32],
+            last_distribution_ts: 0,
+            current_day_start: 1_700_000_000,
+            daily_claimed_amount: 5_000,
+            daily_distributed_to_investors: 1_000,
+            daily_distributed_to_creator: 0,
+            current_page: 1,
+            total_pages: 3,
+            carry_over_dust: 200,
+            daily_dust: 200,
+            total_locked_snapshot: 0,
+            snapshot_pages_done: 3,
+            investor_fee_pool: 2_700,
+            carried_investor_fees: 1_500,
+            day_finalized: true,
+            bump: 255,
+        };
+        // Between days the treasury owes the carried fees and dust
+        assert_eq!(progress.outstanding_investor_fees(), 1_700);
+
+        // Only carried fees seed the pool; accrued dust stays with its investors
+        progress.reset_for_new_day(1_700_086_400, 3);
+        assert_eq!(progress.investor_fee_pool, 1_500);
+        assert_eq!(progress.daily_dust, 0);
+        assert_eq!(progress.daily_distributed_to_investors, 0);
+        assert_eq!(progress.outstanding_investor_fees(), 1_700);
+
+        // Paid and accrued parts of the pool leave the rest outstanding, plus all accruals
+        progress.investor_fee_pool = 4_000;
+        progress.daily_distributed_to_investors = 2_500;
+        progress.daily_dust = 300;
+        progress.carry_over_dust = 500;
+        assert_eq!(progress.outstanding_investor_fees(), 1_700);
+    }
+
+    #[test]
+    fn test_new_day_anchored_to_midnight() {
+        let midnight = 1_700_006_400; // a UTC day boundary
+        let mut progress = DistributionProgress {
+            vault_id: [1u8; 32],
+            last_distribution_ts: 0,
+            current_day_start: 0,
+            daily_claimed_amount: 0,
+            daily_distributed_to_investors: 0,
+            daily_distributed_to_creator: 0,
+            current_page: 0,
+            total_pages: 0,
+            carry_over_dust: 0,
+            daily_dust: 0,
+            total_locked_snapshot: 0,
+            snapshot_pages_done: 0,
+            investor_fee_pool: 0,
+            carried_investor_fees: 0,
+            day_finalized: false,
+            bump: 255,
+        };
+        assert!(progress.is_new_day_ready(midnight + 90));
+
+        // Yesterday's run was late; today's runs sooner after midnight and still opens the day
+        progress.reset_for_new_day(midnight, 1);
+        progress.last_distribution_ts = midnight + 90;
+        assert!(!progress.is_new_day_ready(midnight + 86_399));
+        assert!(progress.is_new_day_ready(midnight + 86_400 + 5));
+    }
+
+    #[test]
+    fn test_investor_fees_to_carry() {
+        let mut progress = DistributionProgress {
+            vault_id: [1u8; 32],
+            last_distribution_ts: 0,
+            current_day_start: 1_700_000_000,
+            daily_claimed_amount: 5_000,
+            daily_distributed_to_investors: 0,
+            daily_distributed_to_creator: 0,
+            current_page: 2,
+            total_pages: 2,
+            carry_over_dust: 0,
+            daily_dust: 0,
+            total_locked_snapshot: 0,
+            snapshot_pages_done: 2,
+            investor_fee_pool: 1_500,
+            carried_investor_fees: 1_500,
+            day_finalized: false,
+            bump: 255,
+        };
+        // Complete day with nothing locked: the carried-in fees stay with investors
+        assert!(progress.is_payout_complete());
+        assert_eq!(progress.investor_fees_to_carry(), 1_500);
+
+        // Complete day that assigned its pool: rounding leftovers go to the creator
+        progress.total_locked_snapshot = 1_000_000;
+        progress.daily_distributed_to_investors = 1_400;
+        progress.daily_dust = 90;
+        assert_eq!(progress.investor_fees_to_carry(), 0);
 
-        self.amount
-            .saturating_sub(unlocked)
-            .saturating_sub(self.withdrawn)
+        // Pages still pending keep everything not yet paid or accrued
+        progress.current_page = 1;
+        assert_eq!(progress.investor_fees_to_carry(), 10);
     }
 }
@@ -0,0 +1,206 @@
+//! CPI into the Tuktuk cron program
+//!
+//! Each vault owns one cron job, whose authority is the vault's cron
+//! authority PDA. The job fires every 24h and runs a single remote
+//! transaction: Tuktuk fetches one transaction from the crank service URL,
+//! checks it is signed by the registered crank signer and executes it. Page
+//! accounts change daily, so it cannot be compiled into the job up front.
+//!
+//! One run carries one Solana transaction, while a day takes `2 * pages + 1`
+//! (every snapshot page, every payout page, then finalize). No instruction
+//! returns follow-up Tuktuk tasks, so the job only opens the day; the crank
+//! service or any cranker submits the rest by hand. If the service is down
+//! or serves an unsigned transaction, the run does nothing.
+
+use crate::constants::MAX_CRANK_URL_LEN;
+use crate::errors::StarInvestorFeesError;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program::invoke_signed;
+
+/// Tuktuk cron PDA seeds (derived under the cron program ID)
+pub const USER_CRON_JOBS_SEED: &[u8] = b"user_cron_jobs";
+pub const CRON_JOB_NAME_MAPPING_SEED: &[u8] = b"cron_job_name_mapping";
+pub const CRON_JOB_TRANSACTION_SEED: &[u8] = b"cron_job_transaction";
+
+/// Index of the vault's only cron transaction
+pub const CRANK_TRANSACTION_INDEX: u32 = 0;
+
+/// Anchor discriminators: sha256("global:<name>")[..8]
+pub mod discriminators {
+    pub const INITIALIZE_CRON_JOB_V0: [u8; 8] = [246, 64, 133, 115, 169, 84, 130, 133];
+    pub const ADD_CRON_TRANSACTION_V0: [u8; 8] = [22, 94, 81, 77, 143, 154, 255, 102];
+    pub const REMOVE_CRON_TRANSACTION_V0: [u8; 8] = [207, 237, 8, 195, 65, 111, 124, 247];
+    pub const CLOSE_CRON_JOB_V0: [u8; 8] = [114, 152, 214, 24, 97, 36, 231, 102];
+}
+
+/// `TransactionSourceV0::RemoteV0` variant index
+const REMOTE_V0_VARIANT: u8 = 1;
+
+#[derive(AnchorSerialize)]
+struct InitializeCronJobArgsV0 {
+    schedule: String,
+    name: String,
+    free_tasks_per_transaction: u8,
+    num_tasks_per_queue_call: u8,
+}
+
+/// The cron job's user index account
+pub fn user_cron_jobs(authority: &Pubkey, cron_program_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[USER_CRON_JOBS_SEED, authority.as_ref()], cron_program_id).0
+}
+
+/// Name → cron job mapping account
+pub fn cron_job_name_mapping(authority: &Pubkey, name: &str, cron_program_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            CRON_JOB_NAME_MAPPING_SEED,
+            authority.as_ref(),
+            name.as_bytes(),
+        ],
+        cron_program_id,
+    )
+    .0
+}
+
+/// Account holding the cron job's transaction at `index`
+pub fn cron_job_transaction(cron_job: &Pubkey, index: u32, cron_program_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            CRON_JOB_TRANSACTION_SEED,
+            cron_job.as_ref(),
+            &index.to_le_bytes(),
+        ],
+        cron_program_id,
+    )
+    .0
+}
+
+/// Check the crank service a remote cron transaction depends on
+pub fn validate_crank_config(url: &str, signer: &Pubkey) -> Result<()> {
+    require!(
+        !url.is_empty() && url.len() <= MAX_CRANK_URL_LEN,
+        StarInvestorFeesError::InvalidCrankConfig
+    );
+    require!(
+        *signer != Pubkey::default(),
+        StarInvestorFeesError::InvalidCrankConfig
+    );
+    Ok(())
+}
+
+/// Instruction data for `initialize_cron_job_v0`
+pub fn initialize_cron_job_data(schedule: &str, name: &str) -> Result<Vec<u8>> {
+    let mut data = discriminators::INITIALIZE_CRON_JOB_V0.to_vec();
+    InitializeCronJobArgsV0 {
+        schedule: schedule.to_string(),
+        name: name.to_string(),
+        free_tasks_per_transaction: 0,
+        num_tasks_per_queue_call: 1,
+    }
+    .serialize(&mut data)?;
+    Ok(data)
+}
+
+/// Instruction data for `add_cron_transaction_v0` with a remote transaction source
+pub fn add_remote_transaction_data(index: u32, url: &str, signer: &Pubkey) -> Result<Vec<u8>> {
+    let mut data = discriminators::ADD_CRON_TRANSACTION_V0.to_vec();
+    index.serialize(&mut data)?;
+    data.push(REMOTE_V0_VARIANT);
+    url.to_string().serialize(&mut data)?;
+    signer.serialize(&mut data)?;
+    Ok(data)
+}
+
+/// Instruction data for `remove_cron_transaction_v0`
+pub fn remove_transaction_data(index: u32) -> Result<Vec<u8>> {
+    let mut data = discriminators::REMOVE_CRON_TRANSACTION_V0.to_vec();
+    index.serialize(&mut data)?;
+    Ok(data)
+}
+
+/// Instruction data for `close_cron_job_v0`
+pub fn close_cron_job_data() -> Vec<u8> {
+    discriminators::CLOSE_CRON_JOB_V0.to_vec()
+}
+
+/// Invoke the cron program, signing for the vault's cron authority
+///
+/// `accounts` are passed in the cron instruction's account order; each
+/// meta copies the signer/writable flags of its account info.
+pub fn invoke_cron<'info>(
+    cron_program: &AccountInfo<'info>,
+    accounts: &[AccountInfo<'info>],
+    data: Vec<u8>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let instruction = Instruction {
+        program_id: cron_program.key(),
+        accounts: accounts
+            .iter()
+            .map(|account| {
+                if account.is_writable {
+                    AccountMeta::new(account.key(), account.is_signer)
+                } else {
+                    AccountMeta::new_readonly(account.key(), account.is_signer)
+                }
+            })
+            .collect(),
+        data,
+    };
+
+    invoke_signed(&instruction, accounts, signer_seeds)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::hash::hash;
+
+    #[test]
+    fn test_discriminators() {
+        for (name, discriminator) in [
+            (
+                "initialize_cron_job_v0",
+                discriminators::INITIALIZE_CRON_JOB_V0,
+            ),
+            (
+                "add_cron_transaction_v0",
+                discriminators::ADD_CRON_TRANSACTION_V0,
+            ),
+            (
+                "remove_cron_transaction_v0",
+                discriminators::REMOVE_CRON_TRANSACTION_V0,
+            ),
+            ("close_cron_job_v0", discriminators::CLOSE_CRON_JOB_V0),
+        ] {
+            let preimage = format!("global:{}", name);
+            assert_eq!(hash(preimage.as_bytes()).to_bytes()[..8], discriminator);
+        }
+    }
+
+    #[test]
+    fn test_add_remote_transaction_data() {
+        let signer = Pubkey::new_unique();
+        let data = add_remote_transaction_data(0, "https://crank", &signer).unwrap();
+
+        assert_eq!(data[..8], discriminators::ADD_CRON_TRANSACTION_V0);
+        assert_eq!(data[8..12], 0u32.to_le_bytes());
+        assert_eq!(data[12], REMOTE_V0_VARIANT);
+        assert_eq!(data[13..17], 13u32.to_le_bytes());
+        assert_eq!(&data[17..30], b"https://crank");
+        assert_eq!(data[30..], signer.to_bytes());
+    }
+
+    #[test]
+    fn test_validate_crank_config() {
+        let signer = Pubkey::new_unique();
+        assert!(validate_crank_config("https://crank", &signer).is_ok());
+
+        // A remote source is useless without a service URL and signer
+        assert!(validate_crank_config("", &signer).is_err());
+        assert!(validate_crank_config(&"a".repeat(MAX_CRANK_URL_LEN + 1), &signer).is_err());
+        assert!(validate_crank_config("https://crank", &Pubkey::default()).is_err());
+    }
+}
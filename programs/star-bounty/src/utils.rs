@@ -56,14 +56,10 @@ pub mod validation {
     use super::*;
 
     /// Validate vault ID matches expected
-    // pub fn validate_vault_id(actual: &[u8; 32], expected: &[u8; 32]) -> Result<()> {
-    //     require_eq!(
-    //         actual,
-    //         expected,
-    //         StarInvestorFeesError::VaultIdMismatch
-    //     );
-    //     Ok(())
-    // }
+    pub fn validate_vault_id(actual: &[u8; 32], expected: &[u8; 32]) -> Result<()> {
+        require!(actual == expected, StarInvestorFeesError::VaultIdMismatch);
+        Ok(())
+    }
 
     /// Validate fee share is within valid range
     pub fn validate_fee_share_bps(bps: u16) -> Result<()> {
@@ -80,6 +76,15 @@ pub mod validation {
         Ok(())
     }
 
+    /// Validate the treasury still holds everything owed to investors
+    pub fn validate_treasury_covers(treasury_balance: u64, owed_to_investors: u64) -> Result<()> {
+        require!(
+            treasury_balance >= owed_to_investors,
+            StarInvestorFeesError::InsufficientTreasuryBalance
+        );
+        Ok(())
+    }
+
     /// Validate timestamp is within valid range
     pub fn validate_timestamp(ts: i64) -> Result<()> {
         require!(ts > 0, StarInvestorFeesError::ProgressStateCorrupted);
@@ -90,6 +95,9 @@ pub mod validation {
 /// PDA derivation utilities
 pub mod pda {
     use super::*;
+    use anchor_lang::system_program::{
+        allocate, assign, create_account, transfer, Allocate, Assign, CreateAccount, Transfer,
+    };
     // use crate::constants::*;
 
     /// Derive position owner PDA with bump verification
@@ -98,13 +106,6 @@ pub mod pda {
         vault_id: &[u8; 32],
         expected_bump: u8,
     ) -> Result<Pubkey> {
-        let seeds = &[
-            VAULT_SEED,
-            vault_id.as_ref(),
-            POSITION_OWNER_SEED,
-            &[expected_bump],
-        ];
-
         let (pda, bump) = Pubkey::find_program_address(
             &[VAULT_SEED, vault_id.as_ref(), POSITION_OWNER_SEED],
             program_id,
@@ -125,11 +126,396 @@ pub mod pda {
     ) -> [&'a [u8]; 4] {
         [VAULT_SEED, vault_id.as_ref(), POSITION_OWNER_SEED, bump]
     }
+
+    /// Create a rent-exempt program-owned PDA at `target` (seeds include the bump)
+    pub fn create_program_account<'info>(
+        target: &AccountInfo<'info>,
+        seeds: &[&[u8]],
+        space: usize,
+        payer: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+        program_id: &Pubkey,
+    ) -> Result<()> {
+        let signer_seeds = &[seeds];
+
+        let rent = Rent::get()?.minimum_balance(space);
+        let current_lamports = target.lamports();
+
+        if current_lamports == 0 {
+            create_account(
+                CpiContext::new_with_signer(
+                    system_program.clone(),
+                    CreateAccount {
+                        from: payer.clone(),
+                        to: target.clone(),
+                    },
+                    signer_seeds,
+                ),
+                rent,
+                space as u64,
+                program_id,
+            )?;
+        } else {
+            // Someone pre-funded the address; top up, allocate and assign instead
+            let shortfall = rent.saturating_sub(current_lamports);
+            if shortfall > 0 {
+                transfer(
+                    CpiContext::new(
+                        system_program.clone(),
+                        Transfer {
+                            from: payer.clone(),
+                            to: target.clone(),
+                        },
+                    ),
+                    shortfall,
+                )?;
+            }
+            allocate(
+                CpiContext::new_with_signer(
+                    system_program.clone(),
+                    Allocate {
+                        account_to_allocate: target.clone(),
+                    },
+                    signer_seeds,
+                ),
+                space as u64,
+            )?;
+            assign(
+                CpiContext::new_with_signer(
+                    system_program.clone(),
+                    Assign {
+                        account_to_assign: target.clone(),
+                    },
+                    signer_seeds,
+                ),
+                program_id,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Investor receipt utilities
+pub mod receipts {
+    use super::*;
+    use crate::state::InvestorReceipt;
+
+    /// Load an investor's receipt PDA, creating it on first use
+    pub fn load_or_create<'info>(
+        receipt_info: &AccountInfo<'info>,
+        stream: &Pubkey,
+        vault_id: &[u8; 32],
+        payer: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+        program_id: &Pubkey,
+    ) -> Result<InvestorReceipt> {
+        if receipt_info.owner == program_id {
+            return load(receipt_info, stream, vault_id, program_id);
+        }
+
+        let (expected, bump) = Pubkey::find_program_address(
+            &[RECEIPT_SEED, vault_id.as_ref(), stream.as_ref()],
+            program_id,
+        );
+        require_keys_eq!(
+            *receipt_info.key,
+            expected,
+            StarInvestorFeesError::InvalidInvestorReceipt
+        );
+
+        pda::create_program_account(
+            receipt_info,
+            &[RECEIPT_SEED, vault_id.as_ref(), stream.as_ref(), &[bump]],
+            InvestorReceipt::LEN,
+            payer,
+            system_program,
+            program_id,
+        )?;
+
+        Ok(InvestorReceipt {
+            vault_id: *vault_id,
+            stream: *stream,
+            last_snapshot_day: 0,
+            last_paid_day: 0,
+            snapshot_locked_amount: 0,
+            recipient: Pubkey::default(),
+            rent_payer: *payer.key,
+            bump,
+        })
+    }
+
+    /// Load an existing investor receipt and check it belongs to the stream
+    pub fn load(
+        receipt_info: &AccountInfo,
+        stream: &Pubkey,
+        vault_id: &[u8; 32],
+        program_id: &Pubkey,
+    ) -> Result<InvestorReceipt> {
+        require_keys_eq!(
+            *receipt_info.owner,
+            *program_id,
+            StarInvestorFeesError::InvestorNotInSnapshot
+        );
+
+        let data = receipt_info.try_borrow_data()?;
+        let receipt = InvestorReceipt::try_deserialize(&mut &data[..])?;
+
+        let expected = Pubkey::create_program_address(
+            &[
+                RECEIPT_SEED,
+                vault_id.as_ref(),
+                stream.as_ref(),
+                &[receipt.bump],
+            ],
+            program_id,
+        )
+        .map_err(|_| StarInvestorFeesError::InvalidInvestorReceipt)?;
+        require_keys_eq!(
+            *receipt_info.key,
+            expected,
+            StarInvestorFeesError::InvalidInvestorReceipt
+        );
+
+        Ok(receipt)
+    }
+
+    /// Write an investor receipt back to its account
+    pub fn save(receipt_info: &AccountInfo, receipt: &InvestorReceipt) -> Result<()> {
+        let mut data = receipt_info.try_borrow_mut_data()?;
+        receipt.try_serialize(&mut &mut data[..])
+    }
+}
+
+/// Per-stream ledger of sub-minimum payouts
+pub mod accruals {
+    use super::*;
+    use crate::state::InvestorAccrual;
+
+    /// Load an investor's accrual PDA, creating an empty one on first use
+    pub fn load_or_create<'info>(
+        accrual_info: &AccountInfo<'info>,
+        stream: &Pubkey,
+        vault_id: &[u8; 32],
+        payer: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+        program_id: &Pubkey,
+    ) -> Result<InvestorAccrual> {
+        if let Some(accrual) = load_if_exists(accrual_info, stream, vault_id, program_id)? {
+            return Ok(accrual);
+        }
+
+        let (_, bump) = Pubkey::find_program_address(
+            &[ACCRUAL_SEED, vault_id.as_ref(), stream.as_ref()],
+            program_id,
+        );
+        pda::create_program_account(
+            accrual_info,
+            &[ACCRUAL_SEED, vault_id.as_ref(), stream.as_ref(), &[bump]],
+            InvestorAccrual::LEN,
+            payer,
+            system_program,
+            program_id,
+        )?;
+
+        Ok(InvestorAccrual {
+            vault_id: *vault_id,
+            stream: *stream,
+            accrued: 0,
+            rent_payer: *payer.key,
+            bump,
+        })
+    }
+
+    /// Load an investor's accrual PDA, or `None` if it has not been created yet
+    pub fn load_if_exists(
+        accrual_info: &AccountInfo,
+        stream: &Pubkey,
+        vault_id: &[u8; 32],
+        program_id: &Pubkey,
+    ) -> Result<Option<InvestorAccrual>> {
+        let (expected, _) = Pubkey::find_program_address(
+            &[ACCRUAL_SEED, vault_id.as_ref(), stream.as_ref()],
+            program_id,
+        );
+        require_keys_eq!(
+            *accrual_info.key,
+            expected,
+            StarInvestorFeesError::InvalidInvestorAccrual
+        );
+
+        if accrual_info.owner != program_id {
+            return Ok(None);
+        }
+        let data = accrual_info.try_borrow_data()?;
+        InvestorAccrual::try_deserialize(&mut &data[..]).map(Some)
+    }
+
+    /// Write an investor accrual back to its account
+    pub fn save(accrual_info: &AccountInfo, accrual: &InvestorAccrual) -> Result<()> {
+        let mut data = accrual_info.try_borrow_mut_data()?;
+        accrual.try_serialize(&mut &mut data[..])
+    }
+
+    /// Split today's share plus the accrued balance into (paid now, accrued after)
+    ///
+    /// Nothing is paid until the combined amount reaches `min_payout`; once it
+    /// does, the whole balance goes out and the accrual resets.
+    pub fn settle(todays_share: u64, accrued: u64, min_payout: u64) -> Result<(u64, u64)> {
+        let total = safe_math::add(todays_share, accrued)?;
+        if total > 0 && total >= min_payout {
+            Ok((total, 0))
+        } else {
+            Ok((0, total))
+        }
+    }
+}
+
+/// Merkle proof utilities for investor roots
+pub mod merkle {
+    use anchor_lang::prelude::Pubkey;
+    use anchor_lang::solana_program::hash::hashv;
+
+    const LEAF_PREFIX: &[u8] = &[0];
+    const NODE_PREFIX: &[u8] = &[1];
+
+    /// Hash an investor leaf: (stream, investor_owner, weight_cap)
+    pub fn leaf(stream: &Pubkey, investor_owner: &Pubkey, weight_cap: u64) -> [u8; 32] {
+        hashv(&[
+            LEAF_PREFIX,
+            stream.as_ref(),
+            investor_owner.as_ref(),
+            &weight_cap.to_le_bytes(),
+        ])
+        .to_bytes()
+    }
+
+    /// Hash two sibling nodes; pairs are sorted so proofs need no direction bits
+    pub fn node(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+        let (left, right) = if a <= b { (a, b) } else { (b, a) };
+        hashv(&[NODE_PREFIX, left, right]).to_bytes()
+    }
+
+    /// Verify that `leaf` is included under `root`
+    pub fn verify(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+        proof
+            .iter()
+            .fold(leaf, |computed, sibling| node(&computed, sibling))
+            == *root
+    }
+}
+
+/// Resolution of the investors a crank page must cover
+pub mod investors {
+    use super::*;
+    use crate::state::{InvestorProof, InvestorRegistryPage, PolicyConfig};
+
+    /// An investor authorized for the current page
+    #[derive(Debug)]
+    pub struct PageInvestor {
+        /// Wallet that must own the investor's quote token account
+        pub recipient: Pubkey,
+
+        /// Maximum locked amount counted for this investor
+        pub weight_cap: u64,
+    }
+
+    /// Split a page's remaining accounts into per-investor groups and return each group's stream
+    ///
+    /// The stream is the first account of every group of `accounts_per_investor`.
+    pub fn page_streams(
+        remaining_accounts: &[AccountInfo],
+        accounts_per_investor: usize,
+    ) -> Result<Vec<Pubkey>> {
+        require!(
+            remaining_accounts
+                .len()
+                .is_multiple_of(accounts_per_investor),
+            StarInvestorFeesError::InvalidRemainingAccounts
+        );
+        require!(
+            remaining_accounts.len() / accounts_per_investor <= MAX_INVESTORS_PER_PAGE,
+            StarInvestorFeesError::TooManyInvestorsPerPage
+        );
+
+        Ok(remaining_accounts
+            .chunks(accounts_per_investor)
+            .map(|investor| *investor[0].key)
+            .collect())
+    }
+
+    /// Check the page's streams against the registry or the Merkle root
+    ///
+    /// Every investor of the page must be present, in order, so the day's
+    /// locked total covers the full investor set.
+    pub fn resolve_page(
+        policy: &PolicyConfig,
+        registry_page: Option<&InvestorRegistryPage>,
+        page_number: u16,
+        streams: &[Pubkey],
+        proofs: &[InvestorProof],
+    ) -> Result<Vec<PageInvestor>> {
+        if policy.uses_merkle_root() {
+            require_eq!(
+                streams.len(),
+                policy.merkle_page_len(page_number),
+                StarInvestorFeesError::InconsistentInvestorData
+            );
+            require_eq!(
+                proofs.len(),
+                streams.len(),
+                StarInvestorFeesError::InconsistentInvestorData
+            );
+
+            streams
+                .iter()
+                .zip(proofs.iter())
+                .map(|(stream, proof)| {
+                    let leaf = merkle::leaf(stream, &proof.investor_owner, proof.weight_cap);
+                    require!(
+                        merkle::verify(&proof.proof, &policy.investor_root, leaf),
+                        StarInvestorFeesError::InvalidMerkleProof
+                    );
+                    Ok(PageInvestor {
+                        recipient: proof.investor_owner,
+                        weight_cap: proof.weight_cap,
+                    })
+                })
+                .collect()
+        } else {
+            let registry_page =
+                registry_page.ok_or(StarInvestorFeesError::InvestorNotRegistered)?;
+            require!(
+                proofs.is_empty(),
+                StarInvestorFeesError::InconsistentInvestorData
+            );
+            require_eq!(
+                streams.len(),
+                registry_page.entries.len(),
+                StarInvestorFeesError::InconsistentInvestorData
+            );
+
+            streams
+                .iter()
+                .zip(registry_page.entries.iter())
+                .map(|(stream, entry)| {
+                    require_keys_eq!(
+                        *stream,
+                        entry.stream,
+                        StarInvestorFeesError::InvestorNotRegistered
+                    );
+                    Ok(PageInvestor {
+                        recipient: entry.recipient,
+                        weight_cap: u64::MAX,
+                    })
+                })
+                .collect()
+        }
+    }
 }
 
 /// Time utilities
 pub mod time {
-    use super::*;
     use crate::constants::SECONDS_PER_DAY;
 
     /// Check if enough time has passed since last distribution
@@ -179,6 +565,31 @@ pub mod fee_calc {
         safe_math::div128(fee, BPS_DENOMINATOR as u128)
     }
 
+    /// Calculate the day's investor pool from the global locked total
+    ///
+    /// Combines f_locked, the eligible share and the optional daily cap so
+    /// every payout page shares the same pool.
+    pub fn calculate_investor_pool(
+        total_claimable: u64,
+        total_locked: u64,
+        y0: u64,
+        base_share_bps: u16,
+        daily_cap: Option<u64>,
+    ) -> Result<u64> {
+        if total_locked == 0 {
+            return Ok(0);
+        }
+
+        let f_locked = calculate_f_locked(total_locked, y0)?;
+        let share_bps = calculate_eligible_share(base_share_bps, f_locked);
+        let investor_fee = calculate_investor_fee(total_claimable, share_bps)?;
+
+        Ok(match daily_cap {
+            Some(cap) => investor_fee.min(cap),
+            None => investor_fee,
+        })
+    }
+
     /// Calculate proportional payout for an investor
     pub fn calculate_proportional_payout(
         total_to_distribute: u64,
@@ -188,6 +599,11 @@ pub mod fee_calc {
         if total_locked == 0 {
             return Ok(0);
         }
+        // A weight above the total means the page and the snapshot disagree
+        require!(
+            investor_locked <= total_locked,
+            StarInvestorFeesError::InconsistentDistributionState
+        );
 
         let payout = safe_math::mul128(total_to_distribute, investor_locked)?;
         safe_math::div128(payout, total_locked as u128)
@@ -197,7 +613,6 @@ pub mod fee_calc {
 /// Event emission helpers
 pub mod events {
     use super::*;
-    use crate::constants::*;
 
     /// Emit position initialized event with validation
     pub fn emit_position_initialized(
@@ -257,6 +672,18 @@ pub mod events {
         Ok(())
     }
 
+    /// Emit dust accumulated event
+    pub fn emit_dust_accumulated(vault_id: [u8; 32], amount: u64, total_dust: u64) -> Result<()> {
+        let timestamp = Clock::get()?.unix_timestamp;
+        emit!(DustAccumulated {
+            vault_id,
+            amount,
+            total_dust,
+            timestamp,
+        });
+        Ok(())
+    }
+
     /// Emit day closed event
     pub fn emit_day_closed(
         vault_id: [u8; 32],
@@ -374,4 +801,97 @@ mod tests {
                 .unwrap();
         assert_eq!(payout, 250_000); // 25% of distribution
     }
+
+    #[test]
+    fn test_investor_pool() {
+        let y0 = 1_000_000_000;
+
+        // 50% locked caps a 70% share at 50%
+        let pool =
+            fee_calc::calculate_investor_pool(1_000_000, 500_000_000, y0, 7000, None).unwrap();
+        assert_eq!(pool, 500_000);
+
+        // Share below f_locked is used as-is
+        let pool =
+            fee_calc::calculate_investor_pool(1_000_000, 900_000_000, y0, 3000, None).unwrap();
+        assert_eq!(pool, 300_000);
+
+        // Daily cap bounds the pool
+        let pool =
+            fee_calc::calculate_investor_pool(1_000_000, 900_000_000, y0, 3000, Some(100_000))
+                .unwrap();
+        assert_eq!(pool, 100_000);
+
+        // More locked than Y0 clamps f_locked at 100% instead of failing
+        let pool = fee_calc::calculate_investor_pool(1_000_000, 2 * y0, y0, 7000, None).unwrap();
+        assert_eq!(pool, 700_000);
+
+        // Nothing locked means nothing for investors
+        let pool = fee_calc::calculate_investor_pool(1_000_000, 0, y0, 3000, None).unwrap();
+        assert_eq!(pool, 0);
+    }
+
+    #[test]
+    fn test_accrual_settle() {
+        // Below the minimum: everything accrues
+        assert_eq!(accruals::settle(400, 0, 1_000).unwrap(), (0, 400));
+        assert_eq!(accruals::settle(400, 400, 1_000).unwrap(), (0, 800));
+
+        // Reaching the minimum pays today's share and the accrued balance together
+        assert_eq!(accruals::settle(400, 800, 1_000).unwrap(), (1_200, 0));
+        assert_eq!(accruals::settle(1_000, 0, 1_000).unwrap(), (1_000, 0));
+
+        // Nothing owed is never a payout, even with no minimum
+        assert_eq!(accruals::settle(0, 0, 0).unwrap(), (0, 0));
+
+        // Fully unlocked streams flush with no minimum: the accrued balance goes out alone
+        assert_eq!(accruals::settle(0, 300, 0).unwrap(), (300, 0));
+    }
+
+    #[test]
+    fn test_merkle_proofs() {
+        let streams: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let owner = Pubkey::new_unique();
+        let leaves: Vec<[u8; 32]> = streams
+            .iter()
+            .map(|stream| merkle::leaf(stream, &owner, 1_000))
+            .collect();
+
+        // Odd leaf is promoted unchanged
+        let ab = merkle::node(&leaves[0], &leaves[1]);
+        let root = merkle::node(&ab, &leaves[2]);
+
+        assert!(merkle::verify(&[leaves[1], leaves[2]], &root, leaves[0]));
+        assert!(merkle::verify(&[leaves[0], leaves[2]], &root, leaves[1]));
+        assert!(merkle::verify(&[ab], &root, leaves[2]));
+
+        // Any change to the leaf tuple breaks the proof
+        let inflated = merkle::leaf(&streams[0], &owner, 1_001);
+        assert!(!merkle::verify(&[leaves[1], leaves[2]], &root, inflated));
+        let other_owner = merkle::leaf(&streams[0], &Pubkey::new_unique(), 1_000);
+        assert!(!merkle::verify(&[leaves[1], leaves[2]], &root, other_owner));
+    }
+
+    #[test]
+    fn test_payouts_share_global_pool() {
+        // Two pages of investors share one pool using the global denominator
+        let pool = 1_000_000;
+        let page_0 = [300_000_000u64, 200_000_000];
+        let page_1 = [500_000_000u64];
+        let total_locked: u64 = page_0.iter().chain(page_1.iter()).sum();
+
+        let paid: u64 = page_0
+            .iter()
+            .chain(page_1.iter())
+            .map(|locked| {
+                fee_calc::calculate_proportional_payout(pool, *locked, total_locked).unwrap()
+            })
+            .sum();
+
+        assert!(paid <= pool);
+        assert_eq!(
+            fee_calc::calculate_proportional_payout(pool, page_0[0], total_locked).unwrap(),
+            300_000
+        );
+    }
 }
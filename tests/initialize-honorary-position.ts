@@ -221,7 +221,8 @@ describe("Initialize Honorary Position", () => {
         investorFeeShareBps,
         dailyCapLamports,
         minPayoutLamports,
-        y0TotalAllocation
+        y0TotalAllocation,
+        { streamflow: {} }
       )
       .accounts({
         authority: authority.publicKey,
@@ -229,6 +230,7 @@ describe("Initialize Honorary Position", () => {
         policy: policyPda,
         progress: progressPda,
         quoteMint: tokenBMint,
+        baseMint: tokenAMint,
         pool,
         position: mockPosition.publicKey,
         systemProgram: SystemProgram.programId,
@@ -245,6 +247,43 @@ describe("Initialize Honorary Position", () => {
     console.log("✓ Policy initialized successfully");
   });
 
+  it("Initializes quote and base treasuries", async () => {
+    const [treasuryAta] = PublicKey.findProgramAddressSync(
+      [Buffer.from("treasury"), vaultId],
+      program.programId
+    );
+    const [baseTreasury] = PublicKey.findProgramAddressSync(
+      [Buffer.from("base_treasury"), vaultId],
+      program.programId
+    );
+
+    const tx = await program.methods
+      .initializeTreasury(Array.from(vaultId))
+      .accounts({
+        authority: authority.publicKey,
+        policy: policyPda,
+        positionOwnerPda,
+        treasuryAta,
+        baseTreasury,
+        quoteMint: tokenBMint,
+        baseMint: tokenAMint,
+        quoteTokenProgram: TOKEN_PROGRAM_ID,
+        baseTokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    console.log("Initialize treasury tx:", tx);
+
+    const quoteTreasury = await getAccount(provider.connection, treasuryAta);
+    assert.equal(quoteTreasury.mint.toBase58(), tokenBMint.toBase58());
+    assert.equal(quoteTreasury.owner.toBase58(), positionOwnerPda.toBase58());
+    const baseTreasuryAccount = await getAccount(provider.connection, baseTreasury);
+    assert.equal(baseTreasuryAccount.mint.toBase58(), tokenAMint.toBase58());
+    console.log("✓ Treasuries initialized successfully");
+  });
+
   it("Initializes honorary position with NFT", async () => {
     // Generate position NFT mint keypair
     positionNftMint = Keypair.generate();